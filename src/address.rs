@@ -0,0 +1,196 @@
+// file: src/address.rs
+// description: EVM address newtype normalized to lowercase, with EIP-55 checksum display and
+//             case-insensitive comparison
+// docs_reference: https://eips.ethereum.org/EIPS/eip-55
+
+use crate::error::{KrystalApiError, Result};
+use crate::utils::address;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A 20-byte EVM address.
+///
+/// Stored lowercase, so equality, hashing and ordering ignore letter casing. Parsing (user
+/// input) accepts all-lowercase, all-uppercase and correctly checksummed mixed-case input;
+/// `Display` prints the EIP-55 checksummed form and serde uses the lowercase form.
+///
+/// Deserializing (API responses) is lenient: the value is only trimmed and lowercased, so an
+/// empty, short or 32-byte identifier does not fail a whole list response. Use
+/// [`is_valid`](Self::is_valid) to check for a well-formed 20-byte address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(into = "String")]
+pub struct Address(String);
+
+impl Address {
+    /// Normalize an address from an API response without validating it
+    pub fn lenient(s: &str) -> Self {
+        Self(s.trim().to_ascii_lowercase())
+    }
+
+    /// Whether this is `0x` followed by 40 hex digits
+    pub fn is_valid(&self) -> bool {
+        address::is_valid_ethereum_address(&self.0)
+    }
+
+    /// The zero address, `0x0000000000000000000000000000000000000000`
+    pub fn zero() -> Self {
+        Self(format!("0x{}", "0".repeat(40)))
+    }
+
+    /// Lowercase `0x`-prefixed form, as sent to the API
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// EIP-55 checksummed form
+    pub fn to_checksum(&self) -> String {
+        address::to_checksum_address(&self.0).unwrap_or_else(|| self.0.clone())
+    }
+
+    /// Whether this is the zero address
+    pub fn is_zero(&self) -> bool {
+        self.is_valid() && self.0[2..].bytes().all(|b| b == b'0')
+    }
+
+    /// Shortened checksummed form for tables, e.g. `0xA0b8...eB48`
+    pub fn short(&self) -> String {
+        address::format_address_default(&self.to_checksum())
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.to_checksum())
+    }
+}
+
+impl FromStr for Address {
+    type Err = KrystalApiError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let trimmed = s.trim();
+        if !address::is_valid_ethereum_address(trimmed) {
+            return Err(KrystalApiError::InvalidParams(format!(
+                "Invalid address '{s}': expected 0x followed by 40 hex digits"
+            )));
+        }
+        if !address::is_valid_ethereum_address_checksum(trimmed) {
+            return Err(KrystalApiError::InvalidParams(format!(
+                "Invalid address '{s}': mixed-case address fails its EIP-55 checksum"
+            )));
+        }
+        Ok(Self(address::normalize_address(trimmed)))
+    }
+}
+
+impl TryFrom<String> for Address {
+    type Error = KrystalApiError;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl TryFrom<&str> for Address {
+    type Error = KrystalApiError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|s| Self::lenient(&s))
+    }
+}
+
+impl From<Address> for String {
+    fn from(address: Address) -> Self {
+        address.0
+    }
+}
+
+impl AsRef<str> for Address {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Address {
+    fn eq(&self, other: &str) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<&str> for Address {
+    fn eq(&self, other: &&str) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<String> for Address {
+    fn eq(&self, other: &String) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+    #[test]
+    fn test_address_parsing_and_checksum() {
+        let checksummed: Address = USDC.parse().unwrap();
+        let lower: Address = USDC.to_lowercase().parse().unwrap();
+        let upper: Address = format!("0x{}", USDC[2..].to_uppercase()).parse().unwrap();
+
+        assert_eq!(checksummed, lower);
+        assert_eq!(lower, upper);
+        assert_eq!(lower.as_str(), USDC.to_lowercase());
+        assert_eq!(lower.to_string(), USDC);
+        assert_eq!(lower.short(), "0xA0b8...eB48");
+
+        // One letter flipped from the checksummed form
+        assert!("0xa0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse::<Address>().is_err());
+        assert!("0x123".parse::<Address>().is_err());
+        assert!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48".parse::<Address>().is_err());
+        assert!(Address::zero().is_zero());
+    }
+
+    #[test]
+    fn test_address_case_insensitive_eq_hash_and_serde() {
+        let a: Address = USDC.parse().unwrap();
+        let b: Address = USDC.to_lowercase().parse().unwrap();
+        assert_eq!(a, USDC.to_uppercase().replace("0X", "0x"));
+
+        let set: HashSet<Address> = [a.clone(), b].into_iter().collect();
+        assert_eq!(set.len(), 1);
+
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, format!("\"{}\"", USDC.to_lowercase()));
+        let back: Address = serde_json::from_str(&format!("\"{USDC}\"")).unwrap();
+        assert_eq!(back, a);
+    }
+
+    #[test]
+    fn test_address_deserialization_is_lenient() {
+        let parse = |s: &str| serde_json::from_value::<Address>(s.into()).unwrap();
+
+        // A wrong checksum in a response is normalized, not rejected
+        let bad_checksum = parse("0xa0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        assert_eq!(bad_checksum, USDC);
+        assert!(bad_checksum.is_valid());
+
+        let pool_id = format!("0x{}", "AB".repeat(32));
+        for odd in ["", "0x123", pool_id.as_str()] {
+            let address = parse(odd);
+            assert_eq!(address.as_str(), odd.to_lowercase());
+            assert!(!address.is_valid() && !address.is_zero());
+        }
+    }
+}
@@ -0,0 +1,405 @@
+// file: src/amount.rs
+// description: Exact token amounts as a big-integer raw value scaled by the token's decimals,
+//             with decimal formatting, arithmetic and USD conversion
+// docs_reference: https://docs.rs/num-bigint/latest/num_bigint/
+
+use crate::error::{KrystalApiError, Result};
+use num_bigint::{BigInt, Sign};
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
+/// A token amount stored exactly as `raw / 10^decimals`.
+///
+/// `raw` is the on-chain integer amount (e.g. wei for an 18-decimal token). Amounts with
+/// different decimals compare and add exactly by scaling to the larger precision.
+#[derive(Debug, Clone)]
+pub struct TokenAmount {
+    raw: BigInt,
+    decimals: u8,
+}
+
+impl TokenAmount {
+    /// Create an amount from a raw integer and the token's decimals
+    pub fn new(raw: impl Into<BigInt>, decimals: u8) -> Self {
+        Self {
+            raw: raw.into(),
+            decimals,
+        }
+    }
+
+    /// Zero with the given decimals
+    pub fn zero(decimals: u8) -> Self {
+        Self::new(0, decimals)
+    }
+
+    /// Parse a raw integer string, e.g. `"1500000000000000000"` with 18 decimals is 1.5
+    pub fn from_raw(raw: &str, decimals: u8) -> Result<Self> {
+        let trimmed = raw.trim();
+        let digits = trimmed.strip_prefix(['-', '+']).unwrap_or(trimmed);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid(raw));
+        }
+        let value = BigInt::from_str(trimmed).map_err(|_| invalid(raw))?;
+        Ok(Self::new(value, decimals))
+    }
+
+    /// Parse a decimal string in token units, e.g. `"1.5"`, keeping exactly `decimals`
+    /// places; fails if the string has more non-zero fractional digits than that
+    pub fn from_units(units: &str, decimals: u8) -> Result<Self> {
+        units
+            .parse::<TokenAmount>()?
+            .rescale(decimals)
+            .ok_or_else(|| invalid(units))
+    }
+
+    /// Raw integer amount
+    pub fn raw(&self) -> &BigInt {
+        &self.raw
+    }
+
+    /// Number of decimal places `raw` is scaled by
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    /// Whether the amount is zero
+    pub fn is_zero(&self) -> bool {
+        self.raw.sign() == Sign::NoSign
+    }
+
+    /// Whether the amount is below zero
+    pub fn is_negative(&self) -> bool {
+        self.raw.sign() == Sign::Minus
+    }
+
+    /// Absolute value
+    pub fn abs(&self) -> Self {
+        Self::new(BigInt::from(self.raw.magnitude().clone()), self.decimals)
+    }
+
+    /// The same amount with different decimals; `None` when reducing the decimals would
+    /// drop non-zero digits
+    pub fn rescale(&self, decimals: u8) -> Option<Self> {
+        match decimals.cmp(&self.decimals) {
+            Ordering::Equal => Some(self.clone()),
+            Ordering::Greater => Some(Self::new(
+                &self.raw * pow10(decimals - self.decimals),
+                decimals,
+            )),
+            Ordering::Less => {
+                let divisor = pow10(self.decimals - decimals);
+                let remainder = &self.raw % &divisor;
+                (remainder.sign() == Sign::NoSign)
+                    .then(|| Self::new(&self.raw / divisor, decimals))
+            }
+        }
+    }
+
+    /// Nearest `f64`, for display, sorting or charting; not exact for large amounts
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// Value in USD at a per-token price
+    pub fn usd_value(&self, price: f64) -> f64 {
+        self.to_f64() * price
+    }
+
+    /// The amount rounded half away from zero to `decimals` places; exact when `decimals`
+    /// is not below the current decimals
+    pub fn round(&self, decimals: u8) -> Self {
+        if let Some(exact) = self.rescale(decimals) {
+            return exact;
+        }
+        let divisor = pow10(self.decimals - decimals);
+        let half = &divisor / 2;
+        let magnitude = BigInt::from(self.raw.magnitude().clone());
+        let mut rounded: BigInt = (magnitude + half) / divisor;
+        if self.is_negative() {
+            rounded = -rounded;
+        }
+        Self::new(rounded, decimals)
+    }
+
+    /// Decimal string rounded half away from zero to `places` fractional digits
+    pub fn format_rounded(&self, places: u8) -> String {
+        if places >= self.decimals {
+            return format_fixed(&self.raw, self.decimals, places);
+        }
+        format_fixed(self.round(places).raw(), places, places)
+    }
+
+    /// Both amounts scaled to the larger of their decimals
+    fn aligned(&self, other: &Self) -> (BigInt, BigInt, u8) {
+        let decimals = self.decimals.max(other.decimals);
+        let scale = |amount: &Self| &amount.raw * pow10(decimals - amount.decimals);
+        (scale(self), scale(other), decimals)
+    }
+}
+
+fn invalid(value: &str) -> KrystalApiError {
+    KrystalApiError::InvalidParams(format!("Invalid token amount: {value}"))
+}
+
+fn pow10(exponent: u8) -> BigInt {
+    BigInt::from(10u8).pow(u32::from(exponent))
+}
+
+/// `raw / 10^decimals` written with exactly `places` fractional digits (`places >= decimals`)
+fn format_fixed(raw: &BigInt, decimals: u8, places: u8) -> String {
+    let digits = raw.magnitude().to_string();
+    let decimals = usize::from(decimals);
+    let padded = format!("{digits:0>width$}", width = decimals + 1);
+    let (int_part, frac_part) = padded.split_at(padded.len() - decimals);
+
+    let mut out = String::new();
+    if raw.sign() == Sign::Minus {
+        out.push('-');
+    }
+    out.push_str(int_part);
+    if places > 0 {
+        out.push('.');
+        out.push_str(frac_part);
+        out.push_str(&"0".repeat(usize::from(places) - decimals));
+    }
+    out
+}
+
+impl fmt::Display for TokenAmount {
+    /// Exact decimal without trailing zeros; a precision (`{:.4}`) rounds to that many places
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match f.precision() {
+            Some(places) => self.format_rounded(u8::try_from(places).unwrap_or(u8::MAX)),
+            None => {
+                let fixed = format_fixed(&self.raw, self.decimals, self.decimals);
+                if fixed.contains('.') {
+                    fixed.trim_end_matches('0').trim_end_matches('.').to_string()
+                } else {
+                    fixed
+                }
+            }
+        };
+
+        // Pad without `Formatter::pad`, which would treat the precision as a truncation
+        let width = f.width().unwrap_or(0);
+        match f.align() {
+            Some(fmt::Alignment::Left) => write!(f, "{text:<width$}"),
+            Some(fmt::Alignment::Center) => write!(f, "{text:^width$}"),
+            _ => write!(f, "{text:>width$}"),
+        }
+    }
+}
+
+impl FromStr for TokenAmount {
+    type Err = KrystalApiError;
+
+    /// Parse a decimal string such as `"-12.3400"`; decimals are the number of fractional
+    /// digits given
+    fn from_str(s: &str) -> Result<Self> {
+        let trimmed = s.trim();
+        let (negative, unsigned) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let (int_part, frac_part) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+
+        let valid = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if (int_part.is_empty() && frac_part.is_empty()) || !valid(int_part) || !valid(frac_part) {
+            return Err(invalid(s));
+        }
+        let decimals = u8::try_from(frac_part.len()).map_err(|_| invalid(s))?;
+
+        let digits = format!("{int_part}{frac_part}");
+        let mut raw = BigInt::parse_bytes(digits.as_bytes(), 10).ok_or_else(|| invalid(s))?;
+        if negative {
+            raw = -raw;
+        }
+        Ok(Self::new(raw, decimals))
+    }
+}
+
+impl PartialEq for TokenAmount {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TokenAmount {}
+
+impl PartialOrd for TokenAmount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TokenAmount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b, _) = self.aligned(other);
+        a.cmp(&b)
+    }
+}
+
+impl Add for TokenAmount {
+    type Output = TokenAmount;
+
+    fn add(self, other: Self) -> Self {
+        &self + &other
+    }
+}
+
+impl Add for &TokenAmount {
+    type Output = TokenAmount;
+
+    fn add(self, other: Self) -> TokenAmount {
+        let (a, b, decimals) = self.aligned(other);
+        TokenAmount::new(a + b, decimals)
+    }
+}
+
+impl Sub for TokenAmount {
+    type Output = TokenAmount;
+
+    fn sub(self, other: Self) -> Self {
+        &self - &other
+    }
+}
+
+impl Sub for &TokenAmount {
+    type Output = TokenAmount;
+
+    fn sub(self, other: Self) -> TokenAmount {
+        let (a, b, decimals) = self.aligned(other);
+        TokenAmount::new(a - b, decimals)
+    }
+}
+
+impl Neg for TokenAmount {
+    type Output = TokenAmount;
+
+    fn neg(self) -> Self {
+        Self::new(-self.raw, self.decimals)
+    }
+}
+
+impl Sum for TokenAmount {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(0), |total, amount| total + amount)
+    }
+}
+
+impl<'a> Sum<&'a TokenAmount> for TokenAmount {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(0), |total, amount| &total + amount)
+    }
+}
+
+/// Serialized as an exact decimal string, e.g. `"1.5"`
+impl Serialize for TokenAmount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Serialize as the nearest JSON number, for fields the API itself sends as numbers
+pub(crate) fn serialize_as_number<S: Serializer>(
+    amount: &TokenAmount,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_f64(amount.to_f64())
+}
+
+/// Accepts a decimal string or a JSON number; floats keep the digits of their shortest
+/// representation, so values already rounded by the API are not made worse
+impl<'de> Deserialize<'de> for TokenAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct AmountVisitor;
+
+        impl Visitor<'_> for AmountVisitor {
+            type Value = TokenAmount;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a decimal string or number")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> std::result::Result<Self::Value, E> {
+                value.parse().map_err(E::custom)
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> std::result::Result<Self::Value, E> {
+                Ok(TokenAmount::new(value, 0))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> std::result::Result<Self::Value, E> {
+                Ok(TokenAmount::new(value, 0))
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> std::result::Result<Self::Value, E> {
+                if !value.is_finite() {
+                    return Err(E::custom(format!("non-finite token amount {value}")));
+                }
+                value.to_string().parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(AmountVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_formatting_and_parsing() {
+        let wei = TokenAmount::from_raw("1234567890123456789012", 18).unwrap();
+        assert_eq!(wei.to_string(), "1234.567890123456789012");
+        assert_eq!(format!("{wei:.4}"), "1234.5679");
+        assert_eq!(format!("{:<10.2}|", TokenAmount::new(-5, 1)), "-0.50     |");
+        assert_eq!(TokenAmount::new(1, 6).to_string(), "0.000001");
+        assert_eq!(TokenAmount::new(-15, 1).format_rounded(0), "-2");
+
+        assert_eq!(TokenAmount::from_units("1.5", 18).unwrap(), wei_of("1500000000000000000"));
+        assert!(TokenAmount::from_units("0.0000001", 6).is_err());
+        assert!(TokenAmount::from_raw("1.5", 18).is_err());
+        assert!("1e5".parse::<TokenAmount>().is_err());
+        assert_eq!("-0.25".parse::<TokenAmount>().unwrap(), TokenAmount::new(-25, 2));
+    }
+
+    fn wei_of(raw: &str) -> TokenAmount {
+        TokenAmount::from_raw(raw, 18).unwrap()
+    }
+
+    #[test]
+    fn test_arithmetic_across_decimals() {
+        let a = wei_of("100000000000000000001");
+        let b = TokenAmount::from_units("0.5", 6).unwrap();
+        assert_eq!((&a + &b).to_string(), "100.500000000000000001");
+        assert_eq!((&b - &a).to_string(), "-99.500000000000000001");
+        assert_eq!(-b.clone(), TokenAmount::new(-5, 1));
+        assert!(a > b);
+        assert_eq!([a.clone(), b.clone()].iter().sum::<TokenAmount>(), a + b);
+        assert_eq!(TokenAmount::new(150, 2).rescale(1), Some(TokenAmount::new(15, 1)));
+        assert_eq!(TokenAmount::new(151, 2).rescale(1), None);
+        assert_eq!(TokenAmount::new(155, 2).round(1), TokenAmount::new(16, 1));
+        assert_eq!(TokenAmount::new(-155, 2).round(1).decimals(), 1);
+        assert_eq!(TokenAmount::new(-155, 2).round(1), TokenAmount::new(-16, 1));
+        assert_eq!(TokenAmount::new(15, 1).round(3).decimals(), 3);
+    }
+
+    #[test]
+    fn test_usd_value_and_serde() {
+        let amount = TokenAmount::from_units("2.5", 18).unwrap();
+        assert_eq!(amount.usd_value(2000.0), 5000.0);
+
+        assert_eq!(serde_json::to_string(&amount).unwrap(), r#""2.5""#);
+        let parsed: Vec<TokenAmount> = serde_json::from_str(r#"["0.1", 0.1, -3, 7]"#).unwrap();
+        assert_eq!(parsed[0], parsed[1]);
+        assert_eq!(parsed[2], TokenAmount::new(-3, 0));
+        assert_eq!(parsed[3].decimals(), 0);
+    }
+}
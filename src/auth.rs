@@ -0,0 +1,151 @@
+// file: src/auth.rs
+// description: API key handling, including a redacting key wrapper with stable fingerprints and
+//             an ordered key pool that fails over to the next key on auth or credit errors
+// docs_reference: https://docs.krystal.app/krystal-cloud/api-reference
+
+use crate::utils::hash::fnv1a_64;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// An API key whose `Debug` output never contains the secret
+#[derive(Clone, PartialEq, Eq)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    /// Wrap a key, trimming surrounding whitespace
+    pub fn new(key: impl AsRef<str>) -> Self {
+        Self(key.as_ref().trim().to_string())
+    }
+
+    /// Short, stable, non-reversible identifier for logs and usage records
+    pub fn fingerprint(&self) -> String {
+        format!("{:08x}", fnv1a_64(&self.0) as u32)
+    }
+
+    /// Whether the key is empty after trimming
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ApiKey([redacted] {})", self.fingerprint())
+    }
+}
+
+impl From<&str> for ApiKey {
+    fn from(key: &str) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<String> for ApiKey {
+    fn from(key: String) -> Self {
+        Self::new(key)
+    }
+}
+
+/// Ordered list of keys; requests use the current key and move to the next one when it
+/// is rejected. Rotation is sticky and never wraps around.
+pub(crate) struct KeyPool {
+    keys: Vec<ApiKey>,
+    current: AtomicUsize,
+}
+
+impl KeyPool {
+    /// Build a pool from keys in priority order, dropping empty keys and duplicates
+    pub(crate) fn new(keys: impl IntoIterator<Item = ApiKey>) -> Self {
+        let mut unique: Vec<ApiKey> = Vec::new();
+        for key in keys {
+            if !key.is_empty() && !unique.contains(&key) {
+                unique.push(key);
+            }
+        }
+        Self {
+            keys: unique,
+            current: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Index and key currently in use
+    pub(crate) fn current(&self) -> (usize, &ApiKey) {
+        let index = self.current.load(Ordering::Acquire);
+        (index, &self.keys[index])
+    }
+
+    /// Mark the key at `index` as rejected. Returns `true` when another key is available
+    /// (either rotated to now, or already rotated to by a concurrent request).
+    pub(crate) fn rotate_from(&self, index: usize) -> bool {
+        if index + 1 >= self.keys.len() {
+            return self.current.load(Ordering::Acquire) != index;
+        }
+        match self
+            .current
+            .compare_exchange(index, index + 1, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => true,
+            Err(actual) => actual != index,
+        }
+    }
+
+    /// Go back to the first key
+    pub(crate) fn reset(&self) {
+        self.current.store(0, Ordering::Release);
+    }
+}
+
+impl fmt::Debug for KeyPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPool")
+            .field("keys", &self.keys)
+            .field("current", &self.current.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_debug_is_redacted() {
+        let key = ApiKey::new("  super-secret  ");
+        let debug = format!("{:?}", key);
+        assert!(!debug.contains("super-secret"));
+        assert!(debug.contains(&key.fingerprint()));
+        assert_eq!(key.expose(), "super-secret");
+    }
+
+    #[test]
+    fn test_key_pool_dedupes_and_rotates() {
+        let pool = KeyPool::new(["a", "", "b", "a", "c"].map(ApiKey::from));
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool.current().0, 0);
+
+        assert!(pool.rotate_from(0));
+        assert_eq!(pool.current().1.expose(), "b");
+        // A stale rotation from an already rejected key just follows the current one
+        assert!(pool.rotate_from(0));
+        assert_eq!(pool.current().0, 1);
+
+        assert!(pool.rotate_from(1));
+        assert!(!pool.rotate_from(2));
+        assert_eq!(pool.current().1.expose(), "c");
+
+        pool.reset();
+        assert_eq!(pool.current().0, 0);
+    }
+}
@@ -0,0 +1,634 @@
+// file: src/blocking.rs
+// description: Synchronous facade over the async API client for callers without a tokio
+//             runtime (build scripts, small batch jobs); enabled with the `blocking` feature
+// docs_reference: https://docs.rs/tokio/latest/tokio/runtime/
+
+use crate::client::{ClientConfig, KrystalApiClient as AsyncClient, RequestOptions};
+use crate::error::Result;
+use crate::models::*;
+use crate::position_id::PositionId;
+use crate::query::*;
+use crate::transport::HttpTransport;
+use crate::usage::UsageReport;
+use crate::utils::circuit_breaker::CircuitState;
+use futures::StreamExt;
+use serde_json::Value;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::runtime::{Builder, Runtime};
+
+/// Blocking API client mirroring [`crate::KrystalApiClient`].
+///
+/// Each call drives the async client on a private single-threaded runtime, so methods
+/// must not be called from inside another tokio runtime.
+///
+/// ```rust,no_run
+/// let client = krystal_cli::blocking::KrystalApiClient::from_env()?;
+/// let chains = client.get_chains()?;
+/// println!("{} chains", chains.len());
+/// # Ok::<(), krystal_cli::KrystalApiError>(())
+/// ```
+#[derive(Debug)]
+pub struct KrystalApiClient {
+    inner: AsyncClient,
+    runtime: Runtime,
+}
+
+impl KrystalApiClient {
+    /// Create a new API client with custom configuration
+    pub fn with_config(api_key: String, config: ClientConfig) -> Result<Self> {
+        Self::wrap(AsyncClient::with_config(api_key, config)?)
+    }
+
+    /// Create a new API client that sends requests through a custom transport
+    pub fn with_transport(
+        api_key: String,
+        config: ClientConfig,
+        transport: Arc<dyn HttpTransport>,
+    ) -> Result<Self> {
+        Self::wrap(AsyncClient::with_transport(api_key, config, transport)?)
+    }
+
+    /// Create a new API client with default configuration
+    pub fn new(api_key: String) -> Result<Self> {
+        Self::wrap(AsyncClient::new(api_key)?)
+    }
+
+    /// Create client from environment variable `KRYSTAL_API_KEY`
+    pub fn from_env() -> Result<Self> {
+        Self::wrap(AsyncClient::from_env()?)
+    }
+
+    fn wrap(inner: AsyncClient) -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { inner, runtime })
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// The wrapped async client
+    pub fn async_client(&self) -> &AsyncClient {
+        &self.inner
+    }
+
+    /// Configuration this client was built with
+    pub fn config(&self) -> &ClientConfig {
+        self.inner.config()
+    }
+
+    /// Request counts, sizes, latencies and status codes per endpoint since creation
+    pub fn usage_report(&self) -> UsageReport {
+        self.inner.usage_report()
+    }
+
+    /// Reset the in-process usage counters (the ledger file is untouched)
+    pub fn reset_usage(&self) {
+        self.inner.reset_usage();
+    }
+
+    /// Fingerprint of the API key currently used for requests (safe to log)
+    pub fn active_key_fingerprint(&self) -> String {
+        self.inner.active_key_fingerprint()
+    }
+
+    /// Number of API keys available for failover
+    pub fn key_count(&self) -> usize {
+        self.inner.key_count()
+    }
+
+    /// Return to the primary API key after failover
+    pub fn reset_key_rotation(&self) {
+        self.inner.reset_key_rotation();
+    }
+
+    /// Circuit breaker state for health checks (`None` when the breaker is disabled)
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.inner.circuit_state()
+    }
+
+    /// Drop all memoized responses
+    pub fn clear_memory_cache(&self) {
+        self.inner.clear_memory_cache();
+    }
+
+    /// Get list of all supported blockchain networks
+    pub fn get_chains(&self) -> Result<Vec<ChainInfo>> {
+        self.block_on(self.inner.get_chains())
+    }
+
+    /// [`get_chains`](Self::get_chains) with [`RequestOptions`]
+    pub fn get_chains_with_options(&self, options: &RequestOptions) -> Result<Vec<ChainInfo>> {
+        self.block_on(self.inner.get_chains_with_options(options))
+    }
+
+    /// Get stats for a specific chain
+    pub fn get_chain_stats(&self, chain_id: u32) -> Result<ChainStats> {
+        self.block_on(self.inner.get_chain_stats(chain_id))
+    }
+
+    /// [`get_chain_stats`](Self::get_chain_stats) with [`RequestOptions`]
+    pub fn get_chain_stats_with_options(
+        &self,
+        chain_id: u32,
+        options: &RequestOptions,
+    ) -> Result<ChainStats> {
+        self.block_on(self.inner.get_chain_stats_with_options(chain_id, options))
+    }
+
+    /// Get pool data with filtering options
+    pub fn get_pools(&self, query: PoolsQuery) -> Result<Vec<Pool>> {
+        self.block_on(self.inner.get_pools(query))
+    }
+
+    /// [`get_pools`](Self::get_pools) with [`RequestOptions`]
+    pub fn get_pools_with_options(
+        &self,
+        query: PoolsQuery,
+        options: &RequestOptions,
+    ) -> Result<Vec<Pool>> {
+        self.block_on(self.inner.get_pools_with_options(query, options))
+    }
+
+    /// Get detailed information about a specific pool
+    pub fn get_pool_detail(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        with_incentives: bool,
+    ) -> Result<Pool> {
+        self.block_on(self.inner.get_pool_detail(
+            chain_id,
+            pool_address,
+            factory_address,
+            with_incentives,
+        ))
+    }
+
+    /// [`get_pool_detail`](Self::get_pool_detail) with [`RequestOptions`]
+    pub fn get_pool_detail_with_options(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        with_incentives: bool,
+        options: &RequestOptions,
+    ) -> Result<Pool> {
+        self.block_on(self.inner.get_pool_detail_with_options(
+            chain_id,
+            pool_address,
+            factory_address,
+            with_incentives,
+            options,
+        ))
+    }
+
+    /// Get historical data for a specific pool
+    pub fn get_pool_historical(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        query: Option<TransactionQuery>,
+    ) -> Result<PoolHistoricalData> {
+        self.block_on(self.inner.get_pool_historical(
+            chain_id,
+            pool_address,
+            factory_address,
+            query,
+        ))
+    }
+
+    /// [`get_pool_historical`](Self::get_pool_historical) with [`RequestOptions`]
+    pub fn get_pool_historical_with_options(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        query: Option<TransactionQuery>,
+        options: &RequestOptions,
+    ) -> Result<PoolHistoricalData> {
+        self.block_on(self.inner.get_pool_historical_with_options(
+            chain_id,
+            pool_address,
+            factory_address,
+            query,
+            options,
+        ))
+    }
+
+    /// Get transactions for a specific pool
+    pub fn get_pool_transactions(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        query: Option<TransactionQuery>,
+    ) -> Result<Vec<Transaction>> {
+        self.block_on(self.inner.get_pool_transactions(
+            chain_id,
+            pool_address,
+            factory_address,
+            query,
+        ))
+    }
+
+    /// [`get_pool_transactions`](Self::get_pool_transactions) with [`RequestOptions`]
+    pub fn get_pool_transactions_with_options(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        query: Option<TransactionQuery>,
+        options: &RequestOptions,
+    ) -> Result<Vec<Transaction>> {
+        self.block_on(self.inner.get_pool_transactions_with_options(
+            chain_id,
+            pool_address,
+            factory_address,
+            query,
+            options,
+        ))
+    }
+
+    /// Get all positions for a wallet
+    pub fn get_positions(&self, query: PositionsQuery) -> Result<Vec<Position>> {
+        self.block_on(self.inner.get_positions(query))
+    }
+
+    /// [`get_positions`](Self::get_positions) with [`RequestOptions`]
+    pub fn get_positions_with_options(
+        &self,
+        query: PositionsQuery,
+        options: &RequestOptions,
+    ) -> Result<Vec<Position>> {
+        self.block_on(self.inner.get_positions_with_options(query, options))
+    }
+
+    /// Get detailed information about a specific position
+    pub fn get_position_detail(&self, chain_id: u32, position_id: &PositionId) -> Result<Position> {
+        self.block_on(self.inner.get_position_detail(chain_id, position_id))
+    }
+
+    /// [`get_position_detail`](Self::get_position_detail) with [`RequestOptions`]
+    pub fn get_position_detail_with_options(
+        &self,
+        chain_id: u32,
+        position_id: &PositionId,
+        options: &RequestOptions,
+    ) -> Result<Position> {
+        self.block_on(self.inner.get_position_detail_with_options(chain_id, position_id, options))
+    }
+
+    /// Get transaction history for a specific position
+    pub fn get_position_transactions(
+        &self,
+        chain_id: u32,
+        wallet: Option<&str>,
+        token_address: &str,
+        token_id: Option<&str>,
+        query: Option<TransactionQuery>,
+    ) -> Result<Vec<Transaction>> {
+        self.block_on(self.inner.get_position_transactions(
+            chain_id,
+            wallet,
+            token_address,
+            token_id,
+            query,
+        ))
+    }
+
+    /// [`get_position_transactions`](Self::get_position_transactions) with [`RequestOptions`]
+    pub fn get_position_transactions_with_options(
+        &self,
+        chain_id: u32,
+        wallet: Option<&str>,
+        token_address: &str,
+        token_id: Option<&str>,
+        query: Option<TransactionQuery>,
+        options: &RequestOptions,
+    ) -> Result<Vec<Transaction>> {
+        self.block_on(self.inner.get_position_transactions_with_options(
+            chain_id,
+            wallet,
+            token_address,
+            token_id,
+            query,
+            options,
+        ))
+    }
+
+    /// Get list of all supported protocols
+    pub fn get_protocols(&self) -> Result<Vec<ProtocolSummary>> {
+        self.block_on(self.inner.get_protocols())
+    }
+
+    /// [`get_protocols`](Self::get_protocols) with [`RequestOptions`]
+    pub fn get_protocols_with_options(
+        &self,
+        options: &RequestOptions,
+    ) -> Result<Vec<ProtocolSummary>> {
+        self.block_on(self.inner.get_protocols_with_options(options))
+    }
+
+    /// Call any API path relative to the base URL and return the JSON body unparsed
+    pub fn get_raw(&self, path: &str, params: &[(&str, &str)]) -> Result<Value> {
+        self.block_on(self.inner.get_raw(path, params))
+    }
+
+    /// [`get_raw`](Self::get_raw) with [`RequestOptions`]
+    pub fn get_raw_with_options(
+        &self,
+        path: &str,
+        params: &[(&str, &str)],
+        options: &RequestOptions,
+    ) -> Result<Value> {
+        self.block_on(self.inner.get_raw_with_options(path, params, options))
+    }
+}
+
+// Convenience methods for common use cases
+impl KrystalApiClient {
+    /// Get top pools by TVL for a specific chain
+    pub fn get_top_pools_by_tvl(&self, chain_id: u32, limit: u32) -> Result<Vec<Pool>> {
+        self.block_on(self.inner.get_top_pools_by_tvl(chain_id, limit))
+    }
+
+    /// [`get_top_pools_by_tvl`](Self::get_top_pools_by_tvl) with [`RequestOptions`]
+    pub fn get_top_pools_by_tvl_with_options(
+        &self,
+        chain_id: u32,
+        limit: u32,
+        options: &RequestOptions,
+    ) -> Result<Vec<Pool>> {
+        self.block_on(self.inner.get_top_pools_by_tvl_with_options(chain_id, limit, options))
+    }
+
+    /// Get top pools by 24h volume for a specific chain
+    pub fn get_top_pools_by_volume(&self, chain_id: u32, limit: u32) -> Result<Vec<Pool>> {
+        self.block_on(self.inner.get_top_pools_by_volume(chain_id, limit))
+    }
+
+    /// [`get_top_pools_by_volume`](Self::get_top_pools_by_volume) with [`RequestOptions`]
+    pub fn get_top_pools_by_volume_with_options(
+        &self,
+        chain_id: u32,
+        limit: u32,
+        options: &RequestOptions,
+    ) -> Result<Vec<Pool>> {
+        self.block_on(self.inner.get_top_pools_by_volume_with_options(chain_id, limit, options))
+    }
+
+    /// Get pools for a specific token
+    pub fn get_pools_for_token(&self, token: &str, chain_id: Option<u32>) -> Result<Vec<Pool>> {
+        self.block_on(self.inner.get_pools_for_token(token, chain_id))
+    }
+
+    /// [`get_pools_for_token`](Self::get_pools_for_token) with [`RequestOptions`]
+    pub fn get_pools_for_token_with_options(
+        &self,
+        token: &str,
+        chain_id: Option<u32>,
+        options: &RequestOptions,
+    ) -> Result<Vec<Pool>> {
+        self.block_on(self.inner.get_pools_for_token_with_options(token, chain_id, options))
+    }
+
+    /// Get pools for a specific protocol
+    pub fn get_pools_for_protocol(
+        &self,
+        protocol: &str,
+        chain_id: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Vec<Pool>> {
+        self.block_on(self.inner.get_pools_for_protocol(protocol, chain_id, limit))
+    }
+
+    /// [`get_pools_for_protocol`](Self::get_pools_for_protocol) with [`RequestOptions`]
+    pub fn get_pools_for_protocol_with_options(
+        &self,
+        protocol: &str,
+        chain_id: Option<u32>,
+        limit: Option<u32>,
+        options: &RequestOptions,
+    ) -> Result<Vec<Pool>> {
+        self.block_on(self.inner.get_pools_for_protocol_with_options(
+            protocol,
+            chain_id,
+            limit,
+            options,
+        ))
+    }
+
+    /// Get open positions for a wallet
+    pub fn get_open_positions(&self, wallet: &str, chain_id: Option<u32>) -> Result<Vec<Position>> {
+        self.block_on(self.inner.get_open_positions(wallet, chain_id))
+    }
+
+    /// [`get_open_positions`](Self::get_open_positions) with [`RequestOptions`]
+    pub fn get_open_positions_with_options(
+        &self,
+        wallet: &str,
+        chain_id: Option<u32>,
+        options: &RequestOptions,
+    ) -> Result<Vec<Position>> {
+        self.block_on(self.inner.get_open_positions_with_options(wallet, chain_id, options))
+    }
+
+    /// Get closed positions for a wallet
+    pub fn get_closed_positions(
+        &self,
+        wallet: &str,
+        chain_id: Option<u32>,
+    ) -> Result<Vec<Position>> {
+        self.block_on(self.inner.get_closed_positions(wallet, chain_id))
+    }
+
+    /// [`get_closed_positions`](Self::get_closed_positions) with [`RequestOptions`]
+    pub fn get_closed_positions_with_options(
+        &self,
+        wallet: &str,
+        chain_id: Option<u32>,
+        options: &RequestOptions,
+    ) -> Result<Vec<Position>> {
+        self.block_on(self.inner.get_closed_positions_with_options(wallet, chain_id, options))
+    }
+
+    /// Get all positions for a wallet (open and closed)
+    pub fn get_all_positions(&self, wallet: &str, chain_id: Option<u32>) -> Result<Vec<Position>> {
+        self.block_on(self.inner.get_all_positions(wallet, chain_id))
+    }
+
+    /// [`get_all_positions`](Self::get_all_positions) with [`RequestOptions`]
+    pub fn get_all_positions_with_options(
+        &self,
+        wallet: &str,
+        chain_id: Option<u32>,
+        options: &RequestOptions,
+    ) -> Result<Vec<Position>> {
+        self.block_on(self.inner.get_all_positions_with_options(wallet, chain_id, options))
+    }
+
+    /// Get pools with full pagination metadata
+    pub fn get_pools_paginated(&self, query: PoolsQuery) -> Result<PaginatedResponse<Pool>> {
+        self.block_on(self.inner.get_pools_paginated(query))
+    }
+
+    /// [`get_pools_paginated`](Self::get_pools_paginated) with [`RequestOptions`]
+    pub fn get_pools_paginated_with_options(
+        &self,
+        query: PoolsQuery,
+        options: &RequestOptions,
+    ) -> Result<PaginatedResponse<Pool>> {
+        self.block_on(self.inner.get_pools_paginated_with_options(query, options))
+    }
+
+    /// Iterate over every pool matching a query, fetching pages lazily.
+    ///
+    /// Blocking counterpart of [`crate::KrystalApiClient::pools_stream`].
+    pub fn pools_iter(
+        &self,
+        query: PoolsQuery,
+        max_items: Option<usize>,
+    ) -> impl Iterator<Item = Result<Pool>> + '_ {
+        self.pools_iter_with_options(query, max_items, RequestOptions::default())
+    }
+
+    /// [`pools_iter`](Self::pools_iter) with [`RequestOptions`] applied to every page
+    pub fn pools_iter_with_options(
+        &self,
+        query: PoolsQuery,
+        max_items: Option<usize>,
+        options: RequestOptions,
+    ) -> impl Iterator<Item = Result<Pool>> + '_ {
+        let mut stream = Box::pin(self.inner.pools_stream_with_options(query, max_items, options));
+        std::iter::from_fn(move || self.block_on(stream.next()))
+    }
+
+    /// Download every transaction of a pool between two Unix timestamps
+    pub fn get_pool_transactions_range(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<Transaction>> {
+        self.block_on(self.inner.get_pool_transactions_range(
+            chain_id,
+            pool_address,
+            factory_address,
+            start,
+            end,
+        ))
+    }
+
+    /// [`get_pool_transactions_range`](Self::get_pool_transactions_range) with [`RequestOptions`]
+    pub fn get_pool_transactions_range_with_options(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        start: u64,
+        end: u64,
+        options: &RequestOptions,
+    ) -> Result<Vec<Transaction>> {
+        self.block_on(self.inner.get_pool_transactions_range_with_options(
+            chain_id,
+            pool_address,
+            factory_address,
+            start,
+            end,
+            options,
+        ))
+    }
+
+    /// Fetch details for many pools, at most `concurrency` requests at a time
+    pub fn get_pool_details(
+        &self,
+        pools: &[(u32, &str)],
+        with_incentives: bool,
+        concurrency: usize,
+    ) -> Vec<Result<Pool>> {
+        self.block_on(
+            self.inner
+                .get_pool_details(pools, with_incentives, concurrency),
+        )
+    }
+
+    /// [`get_pool_details`](Self::get_pool_details) with [`RequestOptions`]
+    pub fn get_pool_details_with_options(
+        &self,
+        pools: &[(u32, &str)],
+        with_incentives: bool,
+        concurrency: usize,
+        options: &RequestOptions,
+    ) -> Vec<Result<Pool>> {
+        self.block_on(self.inner.get_pool_details_with_options(
+            pools,
+            with_incentives,
+            concurrency,
+            options,
+        ))
+    }
+
+    /// Fetch positions for many wallets, at most `concurrency` requests at a time
+    pub fn get_positions_many(
+        &self,
+        wallets: &[&str],
+        chain_id: Option<u32>,
+        concurrency: usize,
+    ) -> Vec<Result<Vec<Position>>> {
+        self.block_on(
+            self.inner
+                .get_positions_many(wallets, chain_id, concurrency),
+        )
+    }
+
+    /// [`get_positions_many`](Self::get_positions_many) with [`RequestOptions`]
+    pub fn get_positions_many_with_options(
+        &self,
+        wallets: &[&str],
+        chain_id: Option<u32>,
+        concurrency: usize,
+        options: &RequestOptions,
+    ) -> Vec<Result<Vec<Position>>> {
+        self.block_on(self.inner.get_positions_many_with_options(
+            wallets,
+            chain_id,
+            concurrency,
+            options,
+        ))
+    }
+
+    /// Get recent transactions for a pool
+    pub fn get_recent_pool_transactions(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        limit: u32,
+    ) -> Result<Vec<Transaction>> {
+        self.block_on(
+            self.inner
+                .get_recent_pool_transactions(chain_id, pool_address, limit),
+        )
+    }
+
+    /// [`get_recent_pool_transactions`](Self::get_recent_pool_transactions) with [`RequestOptions`]
+    pub fn get_recent_pool_transactions_with_options(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        limit: u32,
+        options: &RequestOptions,
+    ) -> Result<Vec<Transaction>> {
+        self.block_on(self.inner.get_recent_pool_transactions_with_options(
+            chain_id,
+            pool_address,
+            limit,
+            options,
+        ))
+    }
+}
@@ -0,0 +1,640 @@
+// file: src/cache.rs
+// description: Opt-in persistent response cache keyed by normalized request URL, with
+//             per-endpoint-family TTLs, refresh mode, and clear/stats maintenance helpers
+// docs_reference: https://doc.rust-lang.org/std/fs/
+
+use crate::error::{KrystalApiError, Result};
+use crate::utils::hash::fnv1a_64;
+use crate::utils::time;
+use bytes::Bytes;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// Group of endpoints sharing a cache TTL
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EndpointFamily {
+    /// `/v1/chains` and `/v1/chains/{chainId}`
+    Chains,
+    /// `/v1/protocols`
+    Protocols,
+    /// `/v1/pools` and `/v1/pools/{chainId}/{poolAddress}`
+    Pools,
+    /// `/v1/pools/{chainId}/{poolAddress}/historical`
+    History,
+    /// Pool and position transaction endpoints
+    Transactions,
+    /// `/v1/positions` and position details
+    Positions,
+    /// Anything else
+    Other,
+}
+
+impl EndpointFamily {
+    /// All families, in display order
+    pub const ALL: [EndpointFamily; 7] = [
+        Self::Chains,
+        Self::Protocols,
+        Self::Pools,
+        Self::History,
+        Self::Transactions,
+        Self::Positions,
+        Self::Other,
+    ];
+
+    /// Classify a request URL by its path segments
+    pub fn from_url(url: &Url) -> Self {
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(|s| s.filter(|seg| !seg.is_empty()).collect())
+            .unwrap_or_default();
+
+        match segments.last().copied() {
+            Some("historical") => return Self::History,
+            Some("transactions") => return Self::Transactions,
+            _ => {}
+        }
+
+        if segments.contains(&"chains") {
+            Self::Chains
+        } else if segments.contains(&"protocols") {
+            Self::Protocols
+        } else if segments.contains(&"pools") {
+            Self::Pools
+        } else if segments.contains(&"positions") {
+            Self::Positions
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Lowercase name used for directories and CLI arguments
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Chains => "chains",
+            Self::Protocols => "protocols",
+            Self::Pools => "pools",
+            Self::History => "history",
+            Self::Transactions => "transactions",
+            Self::Positions => "positions",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl FromStr for EndpointFamily {
+    type Err = KrystalApiError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|family| family.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| KrystalApiError::InvalidParams(format!("Unknown endpoint family: {s}")))
+    }
+}
+
+/// Time-to-live per endpoint family; a zero TTL disables caching for that family
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheTtls {
+    pub chains: Duration,
+    pub protocols: Duration,
+    pub pools: Duration,
+    pub history: Duration,
+    pub transactions: Duration,
+    pub positions: Duration,
+}
+
+impl Default for CacheTtls {
+    fn default() -> Self {
+        Self {
+            chains: Duration::from_secs(24 * 60 * 60),
+            protocols: Duration::from_secs(24 * 60 * 60),
+            pools: Duration::from_secs(5 * 60),
+            history: Duration::from_secs(15 * 60),
+            transactions: Duration::from_secs(60),
+            positions: Duration::ZERO,
+        }
+    }
+}
+
+impl CacheTtls {
+    /// TTL for a family
+    pub fn get(&self, family: EndpointFamily) -> Duration {
+        match family {
+            EndpointFamily::Chains => self.chains,
+            EndpointFamily::Protocols => self.protocols,
+            EndpointFamily::Pools => self.pools,
+            EndpointFamily::History => self.history,
+            EndpointFamily::Transactions => self.transactions,
+            EndpointFamily::Positions => self.positions,
+            EndpointFamily::Other => Duration::ZERO,
+        }
+    }
+
+    /// Override the TTL for a family
+    pub fn set(&mut self, family: EndpointFamily, ttl: Duration) {
+        match family {
+            EndpointFamily::Chains => self.chains = ttl,
+            EndpointFamily::Protocols => self.protocols = ttl,
+            EndpointFamily::Pools => self.pools = ttl,
+            EndpointFamily::History => self.history = ttl,
+            EndpointFamily::Transactions => self.transactions = ttl,
+            EndpointFamily::Positions => self.positions = ttl,
+            EndpointFamily::Other => {}
+        }
+    }
+}
+
+/// Configuration for the on-disk response cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// Directory holding cache entries
+    pub dir: PathBuf,
+    /// Per-family TTLs
+    pub ttls: CacheTtls,
+    /// Skip reads but still write fresh responses (force refresh)
+    pub refresh: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            dir: Self::default_dir(),
+            ttls: CacheTtls::default(),
+            refresh: false,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Create a cache configuration for a directory with default TTLs
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Default cache directory: `$KRYSTAL_CACHE_DIR`, `$XDG_CACHE_HOME/krystal-cli`,
+    /// `$HOME/.cache/krystal-cli`, or the system temp dir as a last resort
+    pub fn default_dir() -> PathBuf {
+        if let Ok(dir) = std::env::var("KRYSTAL_CACHE_DIR") {
+            return PathBuf::from(dir);
+        }
+        if let Ok(dir) = std::env::var("XDG_CACHE_HOME") {
+            return Path::new(&dir).join("krystal-cli");
+        }
+        if let Ok(home) = std::env::var("HOME") {
+            return Path::new(&home).join(".cache").join("krystal-cli");
+        }
+        std::env::temp_dir().join("krystal-cli-cache")
+    }
+}
+
+/// A cached response as stored on disk
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct CacheEntry {
+    url: String,
+    stored_at: u64,
+    response: Box<RawValue>,
+}
+
+/// Cache statistics for a single endpoint family
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct FamilyStats {
+    pub family: EndpointFamily,
+    pub ttl_secs: u64,
+    pub entries: usize,
+    pub expired: usize,
+    pub bytes: u64,
+}
+
+/// Aggregate cache statistics
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct CacheStats {
+    pub dir: PathBuf,
+    pub families: Vec<FamilyStats>,
+    pub total_entries: usize,
+    pub total_expired: usize,
+    pub total_bytes: u64,
+}
+
+/// Persistent response cache stored as one JSON file per normalized URL
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    config: CacheConfig,
+}
+
+impl DiskCache {
+    /// Create a cache over a configuration; the directory is created lazily on first write
+    pub fn new(config: CacheConfig) -> Self {
+        Self { config }
+    }
+
+    /// Cache configuration
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    /// Normalized cache key: the URL with query parameters sorted
+    pub fn cache_key(url: &Url) -> String {
+        let mut pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+        pairs.sort();
+
+        let mut normalized = url.clone();
+        normalized.set_query(None);
+        if !pairs.is_empty() {
+            normalized.query_pairs_mut().extend_pairs(pairs);
+        }
+        normalized.to_string()
+    }
+
+    fn family_dir(&self, family: EndpointFamily) -> PathBuf {
+        self.config.dir.join(family.as_str())
+    }
+
+    fn entry_path(&self, family: EndpointFamily, key: &str) -> PathBuf {
+        self.family_dir(family)
+            .join(format!("{:016x}.json", fnv1a_64(key)))
+    }
+
+    /// Look up a fresh cached response; returns `None` on miss, expiry, refresh mode
+    /// or unreadable entries
+    pub fn get(&self, url: &Url) -> Option<Bytes> {
+        if self.config.refresh {
+            return None;
+        }
+
+        let family = EndpointFamily::from_url(url);
+        let ttl = self.config.ttls.get(family);
+        if ttl.is_zero() {
+            return None;
+        }
+
+        let key = Self::cache_key(url);
+        let contents = fs::read_to_string(self.entry_path(family, &key)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&contents).ok()?;
+
+        // Guard against hash collisions
+        if entry.url != key {
+            return None;
+        }
+
+        let age = time::current_timestamp().saturating_sub(entry.stored_at);
+        if age >= ttl.as_secs() {
+            debug!("Cache entry expired ({}s old) for {}", age, key);
+            return None;
+        }
+
+        Some(Bytes::from(entry.response.get().to_owned()))
+    }
+
+    /// Store a JSON response body; families with a zero TTL are skipped
+    pub fn put(&self, url: &Url, body: &[u8]) -> Result<()> {
+        let family = EndpointFamily::from_url(url);
+        if self.config.ttls.get(family).is_zero() {
+            return Ok(());
+        }
+
+        let key = Self::cache_key(url);
+        let entry = CacheEntry {
+            url: key.clone(),
+            stored_at: time::current_timestamp(),
+            response: serde_json::from_slice(body)?,
+        };
+
+        fs::create_dir_all(self.family_dir(family))?;
+        let path = self.entry_path(family, &key);
+        // Unique per process and write, so concurrent writers never share a temp file
+        static WRITES: AtomicU64 = AtomicU64::new(0);
+        let tmp_path = path.with_extension(format!(
+            "json.{}-{}.tmp",
+            std::process::id(),
+            WRITES.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp_path, serde_json::to_vec(&entry)?)?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Remove cached entries for one family or all of them; returns the number removed
+    pub fn clear(&self, family: Option<EndpointFamily>) -> Result<usize> {
+        let families = match family {
+            Some(f) => vec![f],
+            None => EndpointFamily::ALL.to_vec(),
+        };
+
+        let mut removed = 0;
+        for family in families {
+            for path in Self::entry_files(&self.family_dir(family))? {
+                fs::remove_file(path)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Count entries, expired entries and bytes per family
+    pub fn stats(&self) -> Result<CacheStats> {
+        let now = time::current_timestamp();
+        let mut families = Vec::new();
+
+        for family in EndpointFamily::ALL {
+            let ttl_secs = self.config.ttls.get(family).as_secs();
+            let mut stats = FamilyStats {
+                family,
+                ttl_secs,
+                entries: 0,
+                expired: 0,
+                bytes: 0,
+            };
+
+            for path in Self::entry_files(&self.family_dir(family))? {
+                stats.entries += 1;
+                stats.bytes += fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+                let stored_at = fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|c| serde_json::from_str::<CacheEntry>(&c).ok())
+                    .map(|e| e.stored_at);
+                if stored_at.is_none_or(|t| now.saturating_sub(t) >= ttl_secs) {
+                    stats.expired += 1;
+                }
+            }
+
+            families.push(stats);
+        }
+
+        Ok(CacheStats {
+            dir: self.config.dir.clone(),
+            total_entries: families.iter().map(|f| f.entries).sum(),
+            total_expired: families.iter().map(|f| f.expired).sum(),
+            total_bytes: families.iter().map(|f| f.bytes).sum(),
+            families,
+        })
+    }
+
+    fn entry_files(dir: &Path) -> Result<Vec<PathBuf>> {
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut files = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    }
+}
+
+/// Configuration for the in-process memoization cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryCacheConfig {
+    /// Maximum number of responses kept; least recently used entries are evicted first
+    pub capacity: usize,
+    /// How long a memoized response stays valid
+    pub ttl: Duration,
+}
+
+impl Default for MemoryCacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 256,
+            ttl: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct MemoryEntry {
+    value: Bytes,
+    inserted_at: Instant,
+    last_used: u64,
+}
+
+/// Bounded in-memory LRU of response bodies with a short TTL
+#[derive(Debug)]
+pub struct MemoryCache {
+    config: MemoryCacheConfig,
+    entries: HashMap<String, MemoryEntry>,
+    tick: u64,
+}
+
+impl MemoryCache {
+    /// Create an empty memory cache
+    pub fn new(config: MemoryCacheConfig) -> Self {
+        Self {
+            config,
+            entries: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Look up a fresh entry, marking it as recently used
+    pub fn get(&mut self, key: &str) -> Option<Bytes> {
+        let ttl = self.config.ttl;
+        let expired = self
+            .entries
+            .get(key)
+            .is_some_and(|entry| entry.inserted_at.elapsed() >= ttl);
+        if expired {
+            self.entries.remove(key);
+            return None;
+        }
+
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(key).map(|entry| {
+            entry.last_used = tick;
+            entry.value.clone()
+        })
+    }
+
+    /// Insert an entry, evicting the least recently used one when full
+    pub fn insert(&mut self, key: String, value: Bytes) {
+        if self.config.capacity == 0 {
+            return;
+        }
+
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.config.capacity {
+            let ttl = self.config.ttl;
+            self.entries
+                .retain(|_, entry| entry.inserted_at.elapsed() < ttl);
+
+            if self.entries.len() >= self.config.capacity
+                && let Some(oldest) = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(k, _)| k.clone())
+            {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.tick += 1;
+        self.entries.insert(
+            key,
+            MemoryEntry {
+                value,
+                inserted_at: Instant::now(),
+                last_used: self.tick,
+            },
+        );
+    }
+
+    /// Number of entries currently held (including not yet evicted expired ones)
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drop all entries
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache(name: &str) -> DiskCache {
+        let dir =
+            std::env::temp_dir().join(format!("krystal-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        DiskCache::new(CacheConfig::new(dir))
+    }
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_endpoint_family_classification() {
+        let cases = [
+            ("https://x.test/v1/chains", EndpointFamily::Chains),
+            ("https://x.test/v1/chains/1", EndpointFamily::Chains),
+            ("https://x.test/v1/protocols", EndpointFamily::Protocols),
+            ("https://x.test/v1/pools?chainId=1", EndpointFamily::Pools),
+            ("https://x.test/v1/pools/1/0xabc", EndpointFamily::Pools),
+            (
+                "https://x.test/v1/pools/1/0xabc/historical",
+                EndpointFamily::History,
+            ),
+            (
+                "https://x.test/v1/pools/1/0xabc/transactions",
+                EndpointFamily::Transactions,
+            ),
+            (
+                "https://x.test/v1/positions/1/transactions",
+                EndpointFamily::Transactions,
+            ),
+            (
+                "https://x.test/v1/positions?wallet=0x1",
+                EndpointFamily::Positions,
+            ),
+        ];
+        for (u, family) in cases {
+            assert_eq!(EndpointFamily::from_url(&url(u)), family, "{u}");
+        }
+        assert_eq!(
+            "Pools".parse::<EndpointFamily>().unwrap(),
+            EndpointFamily::Pools
+        );
+        assert!("nope".parse::<EndpointFamily>().is_err());
+    }
+
+    #[test]
+    fn test_cache_key_sorts_query_params() {
+        assert_eq!(
+            DiskCache::cache_key(&url("https://x.test/v1/pools?limit=5&chainId=1")),
+            DiskCache::cache_key(&url("https://x.test/v1/pools?chainId=1&limit=5"))
+        );
+    }
+
+    #[test]
+    fn test_put_get_clear_and_stats() {
+        let cache = temp_cache("roundtrip");
+        let chains = url("https://x.test/v1/chains");
+        let positions = url("https://x.test/v1/positions?wallet=0x1");
+        let body = br#"[{"id": 1}]"#;
+
+        assert!(cache.get(&chains).is_none());
+        cache.put(&chains, body).unwrap();
+        cache.put(&positions, body).unwrap();
+        assert_eq!(cache.get(&chains).as_deref(), Some(&body[..]));
+        // Positions default to a zero TTL and are never stored
+        assert!(cache.get(&positions).is_none());
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.total_entries, 1);
+        assert_eq!(stats.total_expired, 0);
+
+        assert_eq!(cache.clear(None).unwrap(), 1);
+        assert!(cache.get(&chains).is_none());
+        fs::remove_dir_all(&cache.config().dir).unwrap();
+    }
+
+    #[test]
+    fn test_memory_cache_evicts_least_recently_used() {
+        let mut memo = MemoryCache::new(MemoryCacheConfig {
+            capacity: 2,
+            ttl: Duration::from_secs(60),
+        });
+        memo.insert("a".to_string(), Bytes::from("1"));
+        memo.insert("b".to_string(), Bytes::from("2"));
+        assert!(memo.get("a").is_some());
+
+        memo.insert("c".to_string(), Bytes::from("3"));
+        assert_eq!(memo.len(), 2);
+        assert!(memo.get("b").is_none());
+        assert_eq!(memo.get("a"), Some(Bytes::from("1")));
+        assert_eq!(memo.get("c"), Some(Bytes::from("3")));
+    }
+
+    #[test]
+    fn test_memory_cache_ttl_expiry() {
+        let mut memo = MemoryCache::new(MemoryCacheConfig {
+            capacity: 4,
+            ttl: Duration::ZERO,
+        });
+        memo.insert("a".to_string(), Bytes::from("1"));
+        assert!(memo.get("a").is_none());
+        assert!(memo.is_empty());
+    }
+
+    #[test]
+    fn test_refresh_skips_reads_and_zero_ttl_expires() {
+        let cache = temp_cache("refresh");
+        let chains = url("https://x.test/v1/chains");
+        cache.put(&chains, b"[]").unwrap();
+
+        let mut config = cache.config().clone();
+        config.refresh = true;
+        assert!(DiskCache::new(config.clone()).get(&chains).is_none());
+
+        config.refresh = false;
+        config.ttls.set(EndpointFamily::Chains, Duration::ZERO);
+        assert!(DiskCache::new(config).get(&chains).is_none());
+        fs::remove_dir_all(&cache.config().dir).unwrap();
+    }
+}
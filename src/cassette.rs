@@ -0,0 +1,246 @@
+// file: src/cassette.rs
+// description: Record/replay ("cassette") transports that save API traffic to disk with the
+//             API key redacted and serve it back deterministically for offline runs
+// docs_reference: https://docs.rs/serde_json/latest/serde_json/
+
+use crate::error::{KrystalApiError, Result};
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport, TransportFuture};
+use crate::utils::hash::fnv1a_64;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use url::Url;
+
+/// Header carrying the API key; always redacted in cassettes
+const API_KEY_HEADER: &str = "kc-apikey";
+
+/// Record/replay mode for the client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Forward requests to the real transport and save every exchange to the directory
+    Record(PathBuf),
+    /// Serve responses from the directory without touching the network
+    Replay(PathBuf),
+}
+
+/// A single recorded request/response exchange
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct CassetteEntry {
+    /// HTTP method (always GET for this API)
+    pub method: String,
+    /// Full request URL including query parameters
+    pub url: String,
+    /// Request headers with the API key redacted
+    pub request_headers: BTreeMap<String, String>,
+    /// HTTP status code of the response
+    pub status: u16,
+    /// Parsed JSON body, when the response body was valid JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<Value>,
+    /// Raw body text, when the response body was not JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+impl CassetteEntry {
+    fn from_exchange(request: &HttpRequest, response: &HttpResponse) -> Self {
+        let request_headers = request
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if name.as_str().eq_ignore_ascii_case(API_KEY_HEADER) {
+                    "[redacted]".to_string()
+                } else {
+                    value.to_str().unwrap_or("[binary]").to_string()
+                };
+                (name.as_str().to_string(), value)
+            })
+            .collect();
+
+        let (response_json, body) = match serde_json::from_slice::<Value>(&response.body) {
+            Ok(json) => (Some(json), None),
+            Err(_) => (None, Some(response.text())),
+        };
+
+        Self {
+            method: "GET".to_string(),
+            url: request.url.to_string(),
+            request_headers,
+            status: response.status,
+            response: response_json,
+            body,
+        }
+    }
+
+    fn to_response(&self) -> HttpResponse {
+        match (&self.response, &self.body) {
+            (Some(json), _) => HttpResponse::json(self.status, json),
+            (None, Some(body)) => HttpResponse::new(self.status, body.clone()),
+            (None, None) => HttpResponse::new(self.status, Vec::new()),
+        }
+    }
+}
+
+/// File path of the cassette entry for a URL inside a cassette directory
+pub fn cassette_path(dir: &Path, url: &Url) -> PathBuf {
+    let slug: String = url
+        .path()
+        .trim_matches('/')
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .take(80)
+        .collect();
+
+    dir.join(format!("{}-{:016x}.json", slug, fnv1a_64(url.as_str())))
+}
+
+/// Transport wrapper that saves every exchange before returning it
+pub struct RecordingTransport {
+    inner: Arc<dyn HttpTransport>,
+    dir: PathBuf,
+}
+
+impl RecordingTransport {
+    /// Wrap a transport, creating the cassette directory if needed
+    pub fn new(inner: Arc<dyn HttpTransport>, dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { inner, dir })
+    }
+
+    fn save(&self, entry: &CassetteEntry, url: &Url) -> Result<()> {
+        let json = serde_json::to_string_pretty(entry)?;
+        fs::write(cassette_path(&self.dir, url), json)?;
+        Ok(())
+    }
+}
+
+impl HttpTransport for RecordingTransport {
+    fn get(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let recorded = request.clone();
+            let response = self.inner.get(request).await?;
+            let entry = CassetteEntry::from_exchange(&recorded, &response);
+            self.save(&entry, &recorded.url)?;
+            Ok(response)
+        })
+    }
+}
+
+/// Transport that serves previously recorded exchanges from disk
+#[derive(Debug, Clone)]
+pub struct ReplayTransport {
+    dir: PathBuf,
+}
+
+impl ReplayTransport {
+    /// Replay from a cassette directory, which must already exist
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        if !dir.is_dir() {
+            return Err(KrystalApiError::InvalidParams(format!(
+                "Cassette directory not found: {}",
+                dir.display()
+            )));
+        }
+        Ok(Self { dir })
+    }
+
+    fn load(&self, url: &Url) -> Result<CassetteEntry> {
+        let path = cassette_path(&self.dir, url);
+        if !path.exists() {
+            return Err(KrystalApiError::CassetteMiss(url.to_string()));
+        }
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+}
+
+impl HttpTransport for ReplayTransport {
+    fn get(&self, request: HttpRequest) -> TransportFuture<'_> {
+        let result = self.load(&request.url).map(|entry| entry.to_response());
+        Box::pin(async move { result })
+    }
+}
+
+/// Apply a cassette mode on top of a transport
+pub fn wrap_transport(
+    transport: Arc<dyn HttpTransport>,
+    mode: &CassetteMode,
+) -> Result<Arc<dyn HttpTransport>> {
+    Ok(match mode {
+        CassetteMode::Record(dir) => Arc::new(RecordingTransport::new(transport, dir.clone())?),
+        CassetteMode::Replay(dir) => Arc::new(ReplayTransport::new(dir.clone())?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::MockTransport;
+    use reqwest::header::HeaderValue;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("krystal-cassette-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn request(url: &str) -> HttpRequest {
+        let mut request = HttpRequest::new(Url::parse(url).unwrap());
+        let mut key = HeaderValue::from_static("secret-key");
+        key.set_sensitive(true);
+        request.headers.insert("KC-APIKey", key);
+        request
+    }
+
+    #[test]
+    fn test_cassette_path_is_stable_and_distinct() {
+        let dir = Path::new("/tmp/c");
+        let a = Url::parse("https://x.test/v1/pools?chainId=1").unwrap();
+        let b = Url::parse("https://x.test/v1/pools?chainId=56").unwrap();
+        assert_eq!(cassette_path(dir, &a), cassette_path(dir, &a));
+        assert_ne!(cassette_path(dir, &a), cassette_path(dir, &b));
+        assert!(
+            cassette_path(dir, &a)
+                .to_string_lossy()
+                .contains("v1_pools-")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay_round_trip() {
+        let dir = temp_dir("roundtrip");
+        let inner =
+            Arc::new(MockTransport::new().with_json("/v1/chains", serde_json::json!([{"id": 1}])));
+        let recorder = RecordingTransport::new(inner, &dir).unwrap();
+        let url = "https://x.test/v1/chains";
+        recorder.get(request(url)).await.unwrap();
+
+        let saved = fs::read_to_string(cassette_path(&dir, &Url::parse(url).unwrap())).unwrap();
+        assert!(!saved.contains("secret-key"));
+        assert!(saved.contains("[redacted]"));
+
+        let replay = ReplayTransport::new(&dir).unwrap();
+        let response = replay.get(request(url)).await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            serde_json::from_slice::<Value>(&response.body).unwrap(),
+            serde_json::json!([{"id": 1}])
+        );
+
+        let miss = replay.get(request("https://x.test/v1/protocols")).await;
+        assert!(matches!(miss, Err(KrystalApiError::CassetteMiss(_))));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_replay_requires_existing_dir() {
+        assert!(ReplayTransport::new(temp_dir("missing")).is_err());
+    }
+}
@@ -3,11 +3,16 @@
 //             maps user-facing argument types to library model types
 // docs_reference: https://docs.rs/clap/latest/clap/
 
-use crate::KrystalApiClient;
 use crate::cli::commands;
 use crate::cli::output::set_no_color;
-use crate::error::Result;
+use crate::error::{KrystalApiError, Result};
+use crate::{
+    Address, ApiKey, CacheConfig, CassetteMode, ClientConfig, EndpointFamily, KrystalApiClient,
+    ParseMode, PoolsQuery, PositionId, UsageLedger,
+};
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "krystal-cli")]
@@ -24,6 +29,16 @@ pub struct Cli {
     #[arg(short, long)]
     pub api_key: Option<String>,
 
+    /// Fallback API key used when earlier keys are rejected or out of credits; repeatable or
+    /// comma-separated
+    #[arg(
+        long = "fallback-key",
+        env = "KRYSTAL_FALLBACK_API_KEYS",
+        value_name = "KEY",
+        value_delimiter = ','
+    )]
+    pub fallback_keys: Vec<String>,
+
     /// Enable verbose output
     #[arg(short, long)]
     pub verbose: bool,
@@ -35,6 +50,164 @@ pub struct Cli {
     /// Disable colored output
     #[arg(long)]
     pub no_color: bool,
+
+    /// Record every API request and JSON response to a cassette directory
+    #[arg(long, value_name = "DIR", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Serve API responses from a cassette directory instead of the network
+    #[arg(long, value_name = "DIR")]
+    pub replay: Option<PathBuf>,
+
+    /// Disable the on-disk response cache
+    #[arg(long, conflicts_with = "refresh")]
+    pub no_cache: bool,
+
+    /// Ignore cached responses and refetch (fresh responses are still cached)
+    #[arg(long)]
+    pub refresh: bool,
+
+    /// Override a cache TTL, e.g. `pools=600` (families: chains, protocols, pools,
+    /// history, transactions, positions, other)
+    #[arg(long, value_name = "FAMILY=SECONDS", value_parser = parse_cache_ttl)]
+    pub cache_ttl: Vec<(EndpointFamily, u64)>,
+
+    /// Usage ledger file [default: $KRYSTAL_USAGE_LEDGER, else
+    /// ~/.local/share/krystal-cli/usage.jsonl]
+    #[arg(long, value_name = "FILE")]
+    pub usage_ledger: Option<PathBuf>,
+
+    /// Do not record requests in the usage ledger
+    #[arg(long, env = "KRYSTAL_NO_USAGE_LEDGER", conflicts_with = "usage_ledger")]
+    pub no_usage_ledger: bool,
+
+    /// API base URL, e.g. a staging server or local stand-in (plain HTTP is only accepted
+    /// for loopback hosts unless --allow-insecure is set)
+    #[arg(long, env = "KRYSTAL_BASE_URL", value_name = "URL")]
+    pub base_url: Option<String>,
+
+    /// Allow a plain-HTTP --base-url on a non-loopback host
+    #[arg(long, env = "KRYSTAL_ALLOW_INSECURE")]
+    pub allow_insecure: bool,
+
+    /// Send all requests through this proxy
+    #[arg(long, env = "KRYSTAL_PROXY", value_name = "URL")]
+    pub proxy: Option<String>,
+
+    /// Request timeout in seconds (at least 1)
+    #[arg(
+        long,
+        env = "KRYSTAL_TIMEOUT",
+        value_name = "SECS",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub timeout: Option<u64>,
+
+    /// Retries after a transient failure (0 disables retrying)
+    #[arg(long, env = "KRYSTAL_RETRIES", value_name = "N")]
+    pub retries: Option<u32>,
+
+    /// Maximum requests per second (0 = unlimited)
+    #[arg(long, env = "KRYSTAL_RPS", value_name = "N")]
+    pub rps: Option<usize>,
+
+    /// Extra PEM root certificate(s) to trust
+    #[arg(long, env = "KRYSTAL_CA_CERT", value_name = "FILE")]
+    pub ca_cert: Option<PathBuf>,
+
+    /// Fail when a response contains fields the models do not know about
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Check requests against the bundled API spec (api.json) before sending them
+    #[arg(long, value_enum, env = "KRYSTAL_SPEC_CHECK", default_value = "off")]
+    pub spec_check: SpecCheckArg,
+}
+
+impl Cli {
+    /// Build the cache configuration from global options
+    pub fn cache_config(&self) -> CacheConfig {
+        let mut config = CacheConfig {
+            refresh: self.refresh,
+            ..CacheConfig::default()
+        };
+        for (family, secs) in &self.cache_ttl {
+            config.ttls.set(*family, Duration::from_secs(*secs));
+        }
+        config
+    }
+
+    /// Ledger file read by `usage` and appended to by API commands
+    pub fn usage_ledger_path(&self) -> PathBuf {
+        self.usage_ledger
+            .clone()
+            .unwrap_or_else(UsageLedger::default_path)
+    }
+
+    /// Build the client configuration from global options
+    pub fn client_config(&self) -> ClientConfig {
+        let cassette = match (&self.record, &self.replay) {
+            (Some(dir), _) => Some(CassetteMode::Record(dir.clone())),
+            (None, Some(dir)) => Some(CassetteMode::Replay(dir.clone())),
+            (None, None) => None,
+        };
+
+        // `ApiKey::new` trims, so `"a, b,"` yields keys `a` and `b`
+        let api_keys = self
+            .fallback_keys
+            .iter()
+            .map(ApiKey::new)
+            .filter(|key| !key.is_empty())
+            .collect();
+
+        let mut config = ClientConfig {
+            api_keys,
+            cassette,
+            cache: (!self.no_cache).then(|| self.cache_config()),
+            usage_ledger: (!self.no_usage_ledger).then(|| self.usage_ledger_path()),
+            allow_insecure: self.allow_insecure,
+            proxy: self.proxy.clone(),
+            ca_cert: self.ca_cert.clone(),
+            parse_mode: self.parse_mode(),
+            spec_check: self.spec_check.into(),
+            ..ClientConfig::default()
+        };
+        if let Some(base_url) = &self.base_url {
+            config.base_url = base_url.clone();
+        }
+        if let Some(timeout) = self.timeout {
+            config.timeout_secs = timeout;
+        }
+        if let Some(retries) = self.retries {
+            config.retry.max_attempts = retries.saturating_add(1);
+        }
+        if let Some(rps) = self.rps {
+            config.max_requests_per_second = rps;
+        }
+        config
+    }
+
+    /// Strict parsing on request; `schema-check` always parses leniently so it can report drift
+    fn parse_mode(&self) -> ParseMode {
+        if self.strict && !matches!(self.command, Commands::SchemaCheck { .. }) {
+            ParseMode::Strict
+        } else {
+            ParseMode::Lenient
+        }
+    }
+
+    /// Resolve the API key from the flag or environment; replay mode needs no real key
+    fn resolve_api_key(&self) -> Result<String> {
+        if let Some(api_key) = &self.api_key {
+            return Ok(api_key.clone());
+        }
+
+        match std::env::var("KRYSTAL_API_KEY") {
+            Ok(api_key) => Ok(api_key),
+            Err(_) if self.replay.is_some() => Ok("replay".to_string()),
+            Err(e) => Err(e.into()),
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -62,7 +235,16 @@ pub enum Commands {
         #[arg(short, long)]
         chain_id: Option<u32>,
 
-        /// Number of results to return
+        /// Query several chains at once, e.g. `--chains 1,8453,56`; results are merged,
+        /// re-sorted and limited across chains
+        #[arg(long, value_delimiter = ',', conflicts_with_all = ["chain_id", "all_chains"])]
+        chains: Vec<u32>,
+
+        /// Query every supported chain (see `chains`)
+        #[arg(long, conflicts_with = "chain_id")]
+        all_chains: bool,
+
+        /// Number of results to return (one request; see --page-size for --all/--max-items)
         #[arg(short, long, default_value = "10")]
         limit: u32,
 
@@ -72,11 +254,11 @@ pub enum Commands {
 
         /// Token address to filter by
         #[arg(short, long)]
-        token: Option<String>,
+        token: Option<Address>,
 
         /// Factory address to filter by
         #[arg(short, long)]
-        factory: Option<String>,
+        factory: Option<Address>,
 
         /// Sort by criteria
         #[arg(short, long, value_enum)]
@@ -102,6 +284,22 @@ pub enum Commands {
         #[arg(long, default_value = "0")]
         offset: u32,
 
+        /// Fetch every page of results
+        #[arg(long, conflicts_with_all = ["chains", "all_chains"])]
+        all: bool,
+
+        /// Fetch pages until this many pools have been collected
+        #[arg(long, value_name = "N", conflicts_with_all = ["chains", "all_chains"])]
+        max_items: Option<usize>,
+
+        /// Pools per request with --all/--max-items [default: 1000, the API maximum]
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = clap::value_parser!(u32).range(1..=i64::from(PoolsQuery::MAX_LIMIT))
+        )]
+        page_size: Option<u32>,
+
         /// Output format (overrides global setting)
         #[arg(long, value_enum)]
         format: Option<OutputFormat>,
@@ -114,11 +312,11 @@ pub enum Commands {
         chain_id: u32,
 
         /// Pool address
-        pool_address: String,
+        pool_address: Address,
 
         /// Factory address (optional)
         #[arg(short, long)]
-        factory: Option<String>,
+        factory: Option<Address>,
 
         /// Include incentives information
         #[arg(short, long)]
@@ -132,11 +330,11 @@ pub enum Commands {
         chain_id: u32,
 
         /// Pool address
-        pool_address: String,
+        pool_address: Address,
 
         /// Factory address (optional)
         #[arg(short, long)]
-        factory: Option<String>,
+        factory: Option<Address>,
 
         /// Start timestamp (Unix timestamp)
         #[arg(long)]
@@ -158,11 +356,11 @@ pub enum Commands {
         chain_id: u32,
 
         /// Pool address
-        pool_address: String,
+        pool_address: Address,
 
         /// Factory address (optional)
         #[arg(short, long)]
-        factory: Option<String>,
+        factory: Option<Address>,
 
         /// Start timestamp (Unix timestamp)
         #[arg(long)]
@@ -183,13 +381,27 @@ pub enum Commands {
         /// Pagination offset
         #[arg(long, default_value = "0")]
         offset: u32,
+
+        /// Download every transaction in the time range (defaults to the last 24 hours),
+        /// ignoring --limit and --offset
+        #[arg(long)]
+        all: bool,
+
+        /// Keep only transactions of this kind (repeatable). The API cannot filter by type,
+        /// so pages are fetched until --limit matches are found (or every page with --all)
+        #[arg(long = "type", value_enum, value_name = "KIND")]
+        kinds: Vec<TransactionKindArg>,
+
+        /// Show token pair, USD value, sender/recipient and block columns
+        #[arg(short, long)]
+        detailed: bool,
     },
 
     /// Query positions for a wallet
     #[command(alias = "pos")]
     Positions {
         /// Wallet address
-        wallet: String,
+        wallet: Address,
 
         /// Chain ID to filter by
         #[arg(short, long)]
@@ -199,6 +411,10 @@ pub enum Commands {
         #[arg(short, long, value_enum)]
         status: Option<PositionStatusArg>,
 
+        /// Keep only positions in this range state (implies --status when not given)
+        #[arg(long, value_enum)]
+        range: Option<PositionRangeArg>,
+
         /// Protocols to filter by (can be specified multiple times)
         #[arg(short, long)]
         protocols: Vec<String>,
@@ -218,8 +434,8 @@ pub enum Commands {
         /// Chain ID
         chain_id: u32,
 
-        /// Position ID
-        position_id: String,
+        /// Position ID as `{NFPM address}-{token ID}`
+        position_id: PositionId,
     },
 
     /// Get transaction history for a specific position
@@ -230,12 +446,17 @@ pub enum Commands {
 
         /// Wallet address (optional)
         #[arg(short, long)]
-        wallet: Option<String>,
+        wallet: Option<Address>,
+
+        /// Rejects `--token-id` next to a position ID; must come before `position`
+        #[command(flatten)]
+        token_id_conflict: TokenIdConflict,
 
-        /// Token address
-        token_address: String,
+        /// Position ID (`{NFPM address}-{token ID}`) or position manager (NFPM) token address
+        #[arg(value_name = "POSITION")]
+        position: PositionRef,
 
-        /// Token ID (optional)
+        /// Token ID (optional; only with a bare token address)
         #[arg(long)]
         token_id: Option<String>,
 
@@ -254,6 +475,10 @@ pub enum Commands {
         /// Maximum number of transactions to return
         #[arg(short, long, default_value = "50")]
         limit: u32,
+
+        /// Show token pair, USD value, sender/recipient and block columns
+        #[arg(short, long)]
+        detailed: bool,
     },
 
     /// List all supported protocols
@@ -277,6 +502,174 @@ pub enum Commands {
         #[arg(long, value_enum)]
         format: Option<OutputFormat>,
     },
+
+    /// Call any API path and print the JSON response, e.g. for endpoints not yet supported
+    Raw {
+        /// Path relative to the base URL, e.g. v1/pools/1/0xabc/fees
+        path: String,
+
+        /// Query parameter as KEY=VALUE (repeatable)
+        #[arg(short, long = "query", value_name = "KEY=VALUE", value_parser = parse_query_param)]
+        query: Vec<(String, String)>,
+    },
+
+    /// Sample live responses and report fields the models do not know about, plus expected
+    /// optional fields that are missing
+    #[command(name = "schema-check")]
+    SchemaCheck {
+        /// Chain to sample pools, history and transactions from
+        #[arg(short, long, default_value = "1")]
+        chain_id: u32,
+
+        /// Number of pools to sample
+        #[arg(short, long, default_value = "20")]
+        limit: u32,
+
+        /// Also sample positions for this wallet
+        #[arg(short, long)]
+        wallet: Option<Address>,
+
+        /// Exit with an error when unknown fields are found
+        #[arg(long)]
+        fail_on_unknown: bool,
+    },
+
+    /// Manage the on-disk response cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// Show API calls recorded in the local usage ledger
+    Usage {
+        /// Group calls by day or by month
+        #[arg(short, long, value_enum, default_value = "daily")]
+        period: UsagePeriodArg,
+
+        /// Only include calls from the last N days
+        #[arg(short, long)]
+        days: Option<u64>,
+
+        /// Delete the usage ledger
+        #[arg(long)]
+        clear: bool,
+    },
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum CacheAction {
+    /// Remove cached responses
+    Clear {
+        /// Only clear one endpoint family (chains, protocols, pools, history, transactions,
+        /// positions, other)
+        #[arg(long)]
+        family: Option<EndpointFamily>,
+    },
+    /// Show cache entry counts, sizes and expiry per endpoint family
+    Stats,
+}
+
+fn parse_cache_ttl(s: &str) -> std::result::Result<(EndpointFamily, u64), String> {
+    let (family, secs) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected FAMILY=SECONDS, got '{s}'"))?;
+    let family = family.parse::<EndpointFamily>().map_err(|e| e.to_string())?;
+    let secs = secs
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| format!("invalid TTL seconds '{secs}'"))?;
+    Ok((family, secs))
+}
+
+fn parse_query_param(s: &str) -> std::result::Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{s}'"))?;
+    if key.is_empty() {
+        return Err(format!("empty parameter name in '{s}'"));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// A `position-transactions` target: one position, or every position of a position manager
+#[derive(Clone, Debug, PartialEq)]
+pub enum PositionRef {
+    /// `{NFPM address}-{token ID}`
+    Id(PositionId),
+    /// Bare position manager (NFPM) token address
+    TokenAddress(Address),
+}
+
+impl PositionRef {
+    /// `tokenAddress` and `tokenId` to query; the token ID comes from the position ID or
+    /// from `--token-id`
+    pub fn resolve<'a>(
+        &'a self,
+        token_id: Option<&'a str>,
+    ) -> Result<(&'a Address, Option<&'a str>)> {
+        match (self, token_id) {
+            (Self::Id(_), Some(_)) => Err(KrystalApiError::InvalidParams(
+                "--token-id cannot be combined with a position ID".to_string(),
+            )),
+            (Self::Id(id), None) => Ok((id.nfpm(), Some(id.token_id()))),
+            (Self::TokenAddress(address), token_id) => Ok((address, token_id)),
+        }
+    }
+}
+
+impl std::str::FromStr for PositionRef {
+    type Err = KrystalApiError;
+
+    /// A position ID when `s` parses as one, otherwise a token address
+    fn from_str(s: &str) -> Result<Self> {
+        if let Ok(id) = s.parse() {
+            return Ok(Self::Id(id));
+        }
+        s.parse().map(Self::TokenAddress).map_err(|_| {
+            KrystalApiError::InvalidParams(format!(
+                "Invalid position '{s}': expected {{NFPM address}}-{{token ID}} or a token address"
+            ))
+        })
+    }
+}
+
+/// `conflicts_with` for `--token-id` and a position ID, which clap cannot express itself
+/// because both forms share the `POSITION` argument; checked while clap parses
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TokenIdConflict;
+
+impl clap::FromArgMatches for TokenIdConflict {
+    fn from_arg_matches(matches: &clap::ArgMatches) -> std::result::Result<Self, clap::Error> {
+        let position_id = matches!(
+            matches.get_one::<PositionRef>("position"),
+            Some(PositionRef::Id(_))
+        );
+        if position_id && matches.get_one::<String>("token_id").is_some() {
+            return Err(clap::Error::raw(
+                clap::error::ErrorKind::ArgumentConflict,
+                "the argument '--token-id <TOKEN_ID>' cannot be used with a position ID",
+            ));
+        }
+        Ok(Self)
+    }
+
+    fn update_from_arg_matches(
+        &mut self,
+        matches: &clap::ArgMatches,
+    ) -> std::result::Result<(), clap::Error> {
+        *self = Self::from_arg_matches(matches)?;
+        Ok(())
+    }
+}
+
+impl clap::Args for TokenIdConflict {
+    fn augment_args(cmd: clap::Command) -> clap::Command {
+        cmd
+    }
+
+    fn augment_args_for_update(cmd: clap::Command) -> clap::Command {
+        cmd
+    }
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -334,6 +727,96 @@ impl From<PositionStatusArg> for crate::models::PositionStatus {
     }
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum PositionRangeArg {
+    /// Open with the price inside the range
+    In,
+    /// Open with the price outside the range
+    Out,
+    /// Closed positions
+    Closed,
+}
+
+impl From<PositionRangeArg> for crate::models::PositionRangeStatus {
+    fn from(range: PositionRangeArg) -> Self {
+        match range {
+            PositionRangeArg::In => crate::models::PositionRangeStatus::InRange,
+            PositionRangeArg::Out => crate::models::PositionRangeStatus::OutOfRange,
+            PositionRangeArg::Closed => crate::models::PositionRangeStatus::Closed,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum TransactionKindArg {
+    /// Swaps through the pool
+    Swap,
+    /// New liquidity minted
+    Mint,
+    /// Liquidity burned
+    Burn,
+    /// Fees or tokens collected
+    Collect,
+    /// Liquidity added to an existing position
+    IncreaseLiquidity,
+    /// Liquidity removed from an existing position
+    DecreaseLiquidity,
+}
+
+impl From<TransactionKindArg> for crate::models::TransactionKind {
+    fn from(kind: TransactionKindArg) -> Self {
+        match kind {
+            TransactionKindArg::Swap => crate::models::TransactionKind::Swap,
+            TransactionKindArg::Mint => crate::models::TransactionKind::Mint,
+            TransactionKindArg::Burn => crate::models::TransactionKind::Burn,
+            TransactionKindArg::Collect => crate::models::TransactionKind::Collect,
+            TransactionKindArg::IncreaseLiquidity => {
+                crate::models::TransactionKind::IncreaseLiquidity
+            }
+            TransactionKindArg::DecreaseLiquidity => {
+                crate::models::TransactionKind::DecreaseLiquidity
+            }
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum SpecCheckArg {
+    /// Send requests unchecked
+    Off,
+    /// Log mismatches and send anyway
+    Warn,
+    /// Refuse to send mismatching requests
+    Strict,
+}
+
+impl From<SpecCheckArg> for crate::spec::SpecCheck {
+    fn from(mode: SpecCheckArg) -> Self {
+        match mode {
+            SpecCheckArg::Off => crate::spec::SpecCheck::Off,
+            SpecCheckArg::Warn => crate::spec::SpecCheck::Warn,
+            SpecCheckArg::Strict => crate::spec::SpecCheck::Strict,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum UsagePeriodArg {
+    /// One row per endpoint per day
+    Daily,
+    /// One row per endpoint per month
+    Monthly,
+}
+
+impl From<UsagePeriodArg> for crate::usage::UsagePeriod {
+    fn from(period: UsagePeriodArg) -> Self {
+        match period {
+            UsagePeriodArg::Daily => crate::usage::UsagePeriod::Daily,
+            UsagePeriodArg::Monthly => crate::usage::UsagePeriod::Monthly,
+        }
+    }
+}
+
 /// Main CLI runner function
 pub async fn run_cli() -> Result<()> {
     dotenvy::dotenv().ok();
@@ -351,11 +834,23 @@ pub async fn run_cli() -> Result<()> {
         .init();
     }
 
-    let client = if let Some(api_key) = cli.api_key.clone() {
-        KrystalApiClient::new(api_key)?
-    } else {
-        KrystalApiClient::from_env()?
-    };
+    // Cache maintenance is local and needs no API key
+    if let Commands::Cache { action } = &cli.command {
+        return commands::execute_cache_command(action, &cli.cache_config(), &cli.format);
+    }
+
+    // The usage ledger is local too
+    if let Commands::Usage {
+        period,
+        days,
+        clear,
+    } = &cli.command
+    {
+        let ledger = UsageLedger::new(cli.usage_ledger_path());
+        return commands::execute_usage_command(&ledger, *period, *days, *clear, &cli.format);
+    }
+
+    let client = KrystalApiClient::with_config(cli.resolve_api_key()?, cli.client_config())?;
 
     commands::execute_command(cli.command, &client, cli.format).await
 }
@@ -3,27 +3,39 @@
 //             routes results to the appropriate output formatter
 // docs_reference: https://docs.rs/clap/latest/clap/
 
+use crate::cli::app::CacheAction;
 use crate::cli::app::Commands;
 use crate::cli::app::OutputFormat;
-use crate::cli::app::PositionStatusArg;
+use crate::cli::app::{PositionRangeArg, PositionStatusArg};
+use crate::cli::app::UsagePeriodArg;
 use crate::cli::output::*;
 use crate::error::Result;
 use crate::query::*;
 use crate::utils::time;
-use crate::KrystalApiClient;
+use crate::{
+    Address, CacheConfig, DiskCache, KrystalApiClient, KrystalApiError, PositionId,
+    PositionRangeStatus, PositionStatus, RequestOptions, SchemaReport, TransactionKind,
+    UsageLedger,
+};
+use futures::TryStreamExt;
 
 struct PoolCommandArgs {
     chain_id: Option<u32>,
+    chains: Vec<u32>,
+    all_chains: bool,
     limit: u32,
     protocol: Option<String>,
-    token: Option<String>,
-    factory: Option<String>,
+    token: Option<Address>,
+    factory: Option<Address>,
     sort_by: Option<crate::cli::app::PoolSortBy>,
     min_tvl: Option<f64>,
     min_volume: Option<f64>,
     with_incentives: bool,
     detailed: bool,
     offset: u32,
+    all: bool,
+    max_items: Option<usize>,
+    page_size: Option<u32>,
 }
 
 #[derive(Clone, Copy)]
@@ -40,6 +52,25 @@ struct PoolTransactionsArgs<'a> {
     time_range: TimeRangeArgs,
     limit: u32,
     offset: u32,
+    all: bool,
+    kinds: Vec<TransactionKind>,
+    detailed: bool,
+}
+
+struct PositionsArgs {
+    wallet: Address,
+    chain_id: Option<u32>,
+    status: Option<PositionStatusArg>,
+    range: Option<PositionRangeArg>,
+    protocols: Vec<String>,
+    detailed: bool,
+}
+
+struct SchemaCheckArgs<'a> {
+    chain_id: u32,
+    limit: u32,
+    wallet: Option<&'a str>,
+    fail_on_unknown: bool,
 }
 
 struct PositionTransactionsArgs<'a> {
@@ -49,6 +80,7 @@ struct PositionTransactionsArgs<'a> {
     token_id: Option<&'a str>,
     time_range: TimeRangeArgs,
     limit: u32,
+    detailed: bool,
 }
 
 /// Execute a CLI command
@@ -68,6 +100,8 @@ pub async fn execute_command(
         }
         Commands::Pools {
             chain_id,
+            chains,
+            all_chains,
             limit,
             protocol,
             token,
@@ -78,11 +112,16 @@ pub async fn execute_command(
             with_incentives,
             detailed,
             offset,
+            all,
+            max_items,
+            page_size,
             format: cmd_format,
         } => {
             let effective_format = cmd_format.as_ref().unwrap_or(&format);
             let args = PoolCommandArgs {
                 chain_id,
+                chains,
+                all_chains,
                 limit,
                 protocol,
                 token,
@@ -93,6 +132,9 @@ pub async fn execute_command(
                 with_incentives,
                 detailed,
                 offset,
+                all,
+                max_items,
+                page_size,
             };
 
             handle_pools(client, args, effective_format).await
@@ -106,8 +148,8 @@ pub async fn execute_command(
             handle_pool_detail(
                 client,
                 chain_id,
-                &pool_address,
-                factory.as_deref(),
+                pool_address.as_str(),
+                factory.as_ref().map(Address::as_str),
                 with_incentives,
                 &format,
             )
@@ -129,8 +171,8 @@ pub async fn execute_command(
             handle_pool_history(
                 client,
                 chain_id,
-                &pool_address,
-                factory.as_deref(),
+                pool_address.as_str(),
+                factory.as_ref().map(Address::as_str),
                 time_range,
                 &format,
             )
@@ -145,11 +187,14 @@ pub async fn execute_command(
             days_ago,
             limit,
             offset,
+            all,
+            kinds,
+            detailed,
         } => {
             let args = PoolTransactionsArgs {
                 chain_id,
-                pool_address: &pool_address,
-                factory_address: factory.as_deref(),
+                pool_address: pool_address.as_str(),
+                factory_address: factory.as_ref().map(Address::as_str),
                 time_range: TimeRangeArgs {
                     start_time,
                     end_time,
@@ -157,6 +202,9 @@ pub async fn execute_command(
                 },
                 limit,
                 offset,
+                all,
+                kinds: kinds.into_iter().map(Into::into).collect(),
+                detailed,
             };
 
             handle_pool_transactions(client, args, &format).await
@@ -165,21 +213,21 @@ pub async fn execute_command(
             wallet,
             chain_id,
             status,
+            range,
             protocols,
             detailed,
             format: cmd_format,
         } => {
             let effective_format = cmd_format.as_ref().unwrap_or(&format);
-            handle_positions(
-                client,
-                &wallet,
+            let args = PositionsArgs {
+                wallet,
                 chain_id,
                 status,
+                range,
                 protocols,
                 detailed,
-                effective_format,
-            )
-            .await
+            };
+            handle_positions(client, args, effective_format).await
         }
         Commands::PositionDetail {
             chain_id,
@@ -188,24 +236,28 @@ pub async fn execute_command(
         Commands::PositionTransactions {
             chain_id,
             wallet,
-            token_address,
+            token_id_conflict: _,
+            position,
             token_id,
             start_time,
             end_time,
             days_ago,
             limit,
+            detailed,
         } => {
+            let (token_address, token_id) = position.resolve(token_id.as_deref())?;
             let args = PositionTransactionsArgs {
                 chain_id,
-                wallet: wallet.as_deref(),
-                token_address: &token_address,
-                token_id: token_id.as_deref(),
+                wallet: wallet.as_ref().map(Address::as_str),
+                token_address: token_address.as_str(),
+                token_id,
                 time_range: TimeRangeArgs {
                     start_time,
                     end_time,
                     days_ago,
                 },
                 limit,
+                detailed,
             };
 
             handle_position_transactions(client, args, &format).await
@@ -224,7 +276,98 @@ pub async fn execute_command(
             let effective_format = cmd_format.as_ref().unwrap_or(&format);
             handle_chain_stats(client, chain_id, effective_format).await
         }
+        Commands::Raw { path, query } => handle_raw(client, &path, &query).await,
+        Commands::SchemaCheck {
+            chain_id,
+            limit,
+            wallet,
+            fail_on_unknown,
+        } => {
+            let args = SchemaCheckArgs {
+                chain_id,
+                limit,
+                wallet: wallet.as_ref().map(Address::as_str),
+                fail_on_unknown,
+            };
+            handle_schema_check(client, args, &format).await
+        }
+        Commands::Cache { action } => {
+            let config = client.config().cache.clone().unwrap_or_default();
+            execute_cache_command(&action, &config, &format)
+        }
+        Commands::Usage {
+            period,
+            days,
+            clear,
+        } => {
+            let ledger = UsageLedger::new(
+                client
+                    .config()
+                    .usage_ledger
+                    .clone()
+                    .unwrap_or_else(UsageLedger::default_path),
+            );
+            execute_usage_command(&ledger, period, days, clear, &format)
+        }
+    }
+}
+
+/// Execute a cache maintenance command (no API access required)
+pub fn execute_cache_command(
+    action: &CacheAction,
+    config: &CacheConfig,
+    format: &OutputFormat,
+) -> Result<()> {
+    let cache = DiskCache::new(config.clone());
+
+    match action {
+        CacheAction::Clear { family } => {
+            let removed = cache.clear(*family)?;
+            let scope = family.map_or("all families".to_string(), |f| f.as_str().to_string());
+            println!(
+                "Removed {} cached responses ({}) from {}",
+                removed,
+                scope,
+                config.dir.display()
+            );
+        }
+        CacheAction::Stats => {
+            let stats = cache.stats()?;
+            match format {
+                OutputFormat::Json => print_json(&stats)?,
+                OutputFormat::Csv => print_cache_stats_csv(&stats)?,
+                OutputFormat::Table | OutputFormat::Compact => print_cache_stats_table(&stats)?,
+            }
+        }
     }
+
+    Ok(())
+}
+
+/// Execute the usage command against the local ledger (no API access required)
+pub fn execute_usage_command(
+    ledger: &UsageLedger,
+    period: UsagePeriodArg,
+    days: Option<u64>,
+    clear: bool,
+    format: &OutputFormat,
+) -> Result<()> {
+    if clear {
+        ledger.clear()?;
+        println!("Cleared usage ledger at {}", ledger.path().display());
+        return Ok(());
+    }
+
+    let since = days.map_or(0, time::start_of_day_ago);
+    let rows = ledger.summarize(period.into(), since)?;
+
+    match format {
+        OutputFormat::Json => print_json(&rows)?,
+        OutputFormat::Csv => print_usage_csv(&rows)?,
+        OutputFormat::Table | OutputFormat::Compact => print_usage_table(&rows, ledger.path())?,
+    }
+
+    Ok(())
 }
 
 async fn handle_chains(
@@ -264,6 +407,12 @@ async fn handle_pools(
     if let Some(cid) = args.chain_id {
         query = query.chain_id(cid);
     }
+    if args.all_chains {
+        let chains = client.get_chains().await?;
+        query = query.chain_ids(chains.iter().map(|c| c.id));
+    } else if !args.chains.is_empty() {
+        query = query.chain_ids(args.chains);
+    }
     if let Some(proto) = args.protocol {
         query = query.protocol(proto);
     }
@@ -286,7 +435,19 @@ async fn handle_pools(
         query = query.with_incentives(true);
     }
 
-    let pools = client.get_pools(query).await?;
+    let pools = if args.all || args.max_items.is_some() {
+        // Every page costs a request, so default to the largest page the API serves
+        let page_size = args.page_size.unwrap_or_else(|| {
+            let wanted = args.max_items.map_or(u32::MAX, |n| u32::try_from(n).unwrap_or(u32::MAX));
+            wanted.clamp(1, PoolsQuery::MAX_LIMIT)
+        });
+        client
+            .pools_stream(query.limit(page_size), args.max_items)
+            .try_collect::<Vec<_>>()
+            .await?
+    } else {
+        client.get_pools(query).await?
+    };
 
     match format {
         OutputFormat::Json => print_json(&pools)?,
@@ -355,23 +516,78 @@ async fn handle_pool_transactions(
     args: PoolTransactionsArgs<'_>,
     format: &OutputFormat,
 ) -> Result<()> {
-    let query = build_transaction_query(args.time_range, Some(args.limit), Some(args.offset));
-
-    let transactions = client
-        .get_pool_transactions(
-            args.chain_id,
-            args.pool_address,
-            args.factory_address,
-            query,
-        )
-        .await?;
+    let mut transactions = if args.all {
+        let start = match (args.time_range.days_ago, args.time_range.start_time) {
+            (Some(days), _) => time::days_ago(days),
+            (None, Some(start)) => start,
+            (None, None) => time::days_ago(1),
+        };
+        let end = args
+            .time_range
+            .end_time
+            .unwrap_or_else(time::current_timestamp);
+
+        client
+            .get_pool_transactions_range(
+                args.chain_id,
+                args.pool_address,
+                args.factory_address,
+                start,
+                end,
+            )
+            .await?
+    } else if args.kinds.is_empty() {
+        let query = build_transaction_query(args.time_range, Some(args.limit), Some(args.offset));
+
+        client
+            .get_pool_transactions(
+                args.chain_id,
+                args.pool_address,
+                args.factory_address,
+                query,
+            )
+            .await?
+    } else {
+        // The API cannot filter by type, so keep paging until `limit` matches are collected
+        let wanted = args.limit as usize;
+        let base = build_transaction_query(args.time_range, Some(args.limit), None)
+            .unwrap_or_default();
+        let mut matches = Vec::new();
+        let mut offset = args.offset;
+        loop {
+            let page = client
+                .get_pool_transactions(
+                    args.chain_id,
+                    args.pool_address,
+                    args.factory_address,
+                    Some(base.clone().offset(offset)),
+                )
+                .await?;
+            let page_len = page.len();
+            matches.extend(
+                page.into_iter()
+                    .filter(|tx| args.kinds.contains(&tx.transaction_type)),
+            );
+            if matches.len() >= wanted || page_len < wanted {
+                break;
+            }
+            offset = offset.saturating_add(args.limit);
+        }
+        matches.truncate(wanted);
+        matches
+    };
+    if !args.kinds.is_empty() {
+        transactions.retain(|tx| args.kinds.contains(&tx.transaction_type));
+    }
 
     match format {
         OutputFormat::Json => print_json(&transactions)?,
-        OutputFormat::Csv => print_transactions_csv(&transactions)?,
-        OutputFormat::Table | OutputFormat::Compact => {
-            print_transactions_table(&transactions, matches!(format, OutputFormat::Compact))?
-        }
+        OutputFormat::Csv => print_transactions_csv(&transactions, args.detailed)?,
+        OutputFormat::Table | OutputFormat::Compact => print_transactions_table(
+            &transactions,
+            matches!(format, OutputFormat::Compact),
+            args.detailed,
+        )?,
     }
 
     Ok(())
@@ -379,26 +595,31 @@ async fn handle_pool_transactions(
 
 async fn handle_positions(
     client: &KrystalApiClient,
-    wallet: &str,
-    chain_id: Option<u32>,
-    status: Option<PositionStatusArg>,
-    protocols: Vec<String>,
-    detailed: bool,
+    args: PositionsArgs,
     format: &OutputFormat,
 ) -> Result<()> {
-    let mut query = PositionsQuery::new(wallet);
+    let detailed = args.detailed;
+    let range: Option<PositionRangeStatus> = args.range.map(Into::into);
+    let mut query = PositionsQuery::new(args.wallet);
 
-    if let Some(cid) = chain_id {
+    if let Some(cid) = args.chain_id {
         query = query.chain_id(cid);
     }
-    if let Some(status_arg) = status {
-        query = query.status(status_arg.into());
+    match (args.status, &range) {
+        (Some(status_arg), _) => query = query.status(status_arg.into()),
+        // Narrow the API query to the only status the range can match
+        (None, Some(PositionRangeStatus::Closed)) => query = query.status(PositionStatus::Closed),
+        (None, Some(_)) => query = query.status(PositionStatus::Open),
+        (None, None) => {}
     }
-    if !protocols.is_empty() {
-        query = query.protocols(protocols);
+    if !args.protocols.is_empty() {
+        query = query.protocols(args.protocols);
     }
 
-    let positions = client.get_positions(query).await?;
+    let mut positions = client.get_positions(query).await?;
+    if let Some(range) = &range {
+        positions.retain(|position| &position.status == range);
+    }
 
     match format {
         OutputFormat::Json => print_json(&positions)?,
@@ -416,7 +637,7 @@ async fn handle_positions(
 async fn handle_position_detail(
     client: &KrystalApiClient,
     chain_id: u32,
-    position_id: &str,
+    position_id: &PositionId,
     format: &OutputFormat,
 ) -> Result<()> {
     let position = client.get_position_detail(chain_id, position_id).await?;
@@ -448,10 +669,12 @@ async fn handle_position_transactions(
 
     match format {
         OutputFormat::Json => print_json(&transactions)?,
-        OutputFormat::Csv => print_transactions_csv(&transactions)?,
-        OutputFormat::Table | OutputFormat::Compact => {
-            print_transactions_table(&transactions, matches!(format, OutputFormat::Compact))?
-        }
+        OutputFormat::Csv => print_transactions_csv(&transactions, args.detailed)?,
+        OutputFormat::Table | OutputFormat::Compact => print_transactions_table(
+            &transactions,
+            matches!(format, OutputFormat::Compact),
+            args.detailed,
+        )?,
     }
 
     Ok(())
@@ -474,7 +697,7 @@ async fn handle_protocols(
                         "{},{},{},{}",
                         escape_csv(&p.key),
                         escape_csv(&p.name),
-                        p.factory_address.as_deref().unwrap_or(""),
+                        p.factory_address.as_ref().map_or("", |a| a.as_str()),
                         p.logo.as_deref().unwrap_or("")
                     );
                 }
@@ -495,7 +718,7 @@ async fn handle_protocols(
                         "{:<20} {:<25} {:<44}",
                         truncate_string(&p.key, 20),
                         truncate_string(&p.name, 25),
-                        p.factory_address.as_deref().unwrap_or("N/A")
+                        p.factory_address.as_ref().map_or("N/A".to_string(), |a| a.to_string())
                     );
                 }
             } else {
@@ -525,6 +748,19 @@ async fn handle_chain_stats(
     Ok(())
 }
 
+async fn handle_raw(
+    client: &KrystalApiClient,
+    path: &str,
+    query: &[(String, String)],
+) -> Result<()> {
+    let params: Vec<(&str, &str)> = query
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+    let json = client.get_raw(path, &params).await?;
+    print_json(&json)
+}
+
 /// Helper function to build transaction query from various time parameters
 fn build_transaction_query(
     time_range: TimeRangeArgs,
@@ -563,3 +799,57 @@ fn build_transaction_query(
         None
     }
 }
+
+/// Sample each endpoint family and collect per-model drift
+async fn handle_schema_check(
+    client: &KrystalApiClient,
+    args: SchemaCheckArgs<'_>,
+    format: &OutputFormat,
+) -> Result<()> {
+    let mut report = SchemaReport::new();
+    // Cached bodies can be hours old and would hide the drift this command looks for
+    let fresh = RequestOptions::new().bypass_cache(true);
+
+    report.observe_all(&client.get_chains_with_options(&fresh).await?);
+    report.observe_all(&client.get_protocols_with_options(&fresh).await?);
+    let stats = client.get_chain_stats_with_options(args.chain_id, &fresh).await?;
+    report.observe_all(std::slice::from_ref(&stats));
+
+    let query = PoolsQuery::new().chain_id(args.chain_id).limit(args.limit);
+    let pools = client.get_pools_with_options(query, &fresh).await?;
+    report.observe_all(&pools);
+
+    if let Some(pool) = pools.first() {
+        let address = pool.address.as_str();
+        let history = client
+            .get_pool_historical_with_options(args.chain_id, address, None, None, &fresh)
+            .await?;
+        report.observe_all(std::slice::from_ref(&history));
+
+        let query = TransactionQuery::new().limit(50);
+        let transactions = client
+            .get_pool_transactions_with_options(args.chain_id, address, None, Some(query), &fresh)
+            .await?;
+        report.observe_all(&transactions);
+    }
+
+    if let Some(wallet) = args.wallet {
+        let positions = client.get_all_positions_with_options(wallet, None, &fresh).await?;
+        report.observe_all(&positions);
+    }
+
+    match format {
+        OutputFormat::Json => print_json(&report)?,
+        OutputFormat::Csv => print_schema_report_csv(&report)?,
+        OutputFormat::Table | OutputFormat::Compact => print_schema_report_table(&report)?,
+    }
+
+    if args.fail_on_unknown && report.has_unknown_fields() {
+        return Err(KrystalApiError::SchemaDrift {
+            context: "schema-check".to_string(),
+            fields: report.unknown_fields(),
+        });
+    }
+
+    Ok(())
+}
@@ -6,10 +6,14 @@
 
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use crate::cache::CacheStats;
+use crate::usage::UsageSummaryRow;
 use crate::error::Result;
 use crate::models::*;
+use crate::schema::SchemaReport;
 use crate::utils::{address, finance};
 use serde::Serialize;
+use std::path::Path;
 
 static NO_COLOR: AtomicBool = AtomicBool::new(false);
 
@@ -193,7 +197,7 @@ pub fn print_pools_csv(pools: &[Pool], detailed: bool) -> Result<()> {
                 i + 1,
                 chain_info.map(|c| c.id).unwrap_or(0),
                 escape_csv(&chain_info.map_or("Unknown".to_string(), |c| c.name.clone())),
-                escape_csv(&pool.address),
+                pool.address.as_str(),
                 escape_csv(&protocol_name),
                 escape_csv(&token0_symbol),
                 escape_csv(&token1_symbol),
@@ -391,11 +395,11 @@ pub fn print_positions_csv(positions: &[Position], detailed: bool) -> Result<()>
                 escape_csv(&pos.id),
                 chain_info.map(|c| c.id).unwrap_or(0),
                 escape_csv(chain_name),
-                escape_csv(&pos.status),
+                escape_csv(pos.status.as_str()),
                 pos.current_position_value,
                 pos.min_price,
                 pos.max_price,
-                escape_csv(&pos.liquidity)
+                pos.liquidity
             );
         }
     } else {
@@ -405,7 +409,7 @@ pub fn print_positions_csv(positions: &[Position], detailed: bool) -> Result<()>
                 "{},{},{},{}",
                 i + 1,
                 escape_csv(&pos.id),
-                escape_csv(&pos.status),
+                escape_csv(pos.status.as_str()),
                 pos.current_position_value
             );
         }
@@ -416,18 +420,16 @@ pub fn print_positions_csv(positions: &[Position], detailed: bool) -> Result<()>
 /// Print detailed position information
 pub fn print_position_detail(position: &Position) -> Result<()> {
     println!("\n{}", color_bold(&format!("Position: {}", position.id)));
-    println!(
-        "Owner: {}",
-        address::format_address_default(&position.owner_address)
-    );
+    println!("Owner: {}", position.owner_address.short());
     println!("Token Address: {}", position.token_address);
     println!("Token ID: {}", position.token_id);
 
-    let status_colored = match position.status.to_uppercase().as_str() {
-        "IN_RANGE" => color_green(&position.status),
-        "OUT_RANGE" | "OUT_OF_RANGE" => color_yellow(&position.status),
-        "CLOSED" => color_red(&position.status),
-        _ => position.status.clone(),
+    let status = position.status.as_str();
+    let status_colored = match position.status {
+        PositionRangeStatus::InRange => color_green(status),
+        PositionRangeStatus::OutOfRange => color_yellow(status),
+        PositionRangeStatus::Closed => color_red(status),
+        PositionRangeStatus::Unknown(_) => status.to_string(),
     };
     println!("Status: {}", status_colored);
     println!("Liquidity: {}", position.liquidity);
@@ -512,8 +514,12 @@ pub fn print_position_detail(position: &Position) -> Result<()> {
     Ok(())
 }
 
-/// Print transactions in table format
-pub fn print_transactions_table(transactions: &[Transaction], compact: bool) -> Result<()> {
+/// Print transactions in table format; `detailed` adds pair, USD value, participants and block
+pub fn print_transactions_table(
+    transactions: &[Transaction],
+    compact: bool,
+    detailed: bool,
+) -> Result<()> {
     if transactions.is_empty() {
         println!("No transactions found");
         return Ok(());
@@ -531,6 +537,33 @@ pub fn print_transactions_table(transactions: &[Transaction], compact: bool) ->
                 tx.amount1
             );
         }
+    } else if detailed {
+        println!(
+            "{:<12} {:<10} {:<12} {:<15} {:<15} {:<10} {:<13} {:<13} {:<10} {:<16}",
+            "Hash", "Type", "Pair", "Amount0", "Amount1", "USD", "Sender", "Recipient", "Block",
+            "Time"
+        );
+        println!("{}", "-".repeat(133));
+
+        let short = |address: &Option<crate::Address>| {
+            address.as_ref().map_or("-".to_string(), |a| a.short())
+        };
+        for tx in transactions {
+            let time_str = crate::utils::time::format_timestamp(tx.timestamp);
+            println!(
+                "{:<12} {:<10} {:<12} {:<15.4} {:<15.4} {:<10} {:<13} {:<13} {:<10} {:<16}",
+                hash_prefix(&tx.hash),
+                truncate_string(tx.transaction_type.as_str(), 10),
+                truncate_string(tx.pair_symbol().as_deref().unwrap_or("-"), 12),
+                tx.amount0,
+                tx.amount1,
+                tx.value_usd.map_or("-".to_string(), finance::format_usd),
+                short(&tx.sender),
+                short(&tx.recipient),
+                tx.block_number.map_or("-".to_string(), |b| b.to_string()),
+                truncate_string(&time_str, 16)
+            );
+        }
     } else {
         println!(
             "{:<12} {:<10} {:<15} {:<15} {:<20}",
@@ -543,7 +576,7 @@ pub fn print_transactions_table(transactions: &[Transaction], compact: bool) ->
             println!(
                 "{:<12} {:<10} {:<15.4} {:<15.4} {:<20}",
                 hash_prefix(&tx.hash),
-                truncate_string(&tx.transaction_type, 10),
+                truncate_string(tx.transaction_type.as_str(), 10),
                 tx.amount0,
                 tx.amount1,
                 truncate_string(&time_str, 20)
@@ -554,17 +587,46 @@ pub fn print_transactions_table(transactions: &[Transaction], compact: bool) ->
     Ok(())
 }
 
-/// Print transactions in CSV format
-pub fn print_transactions_csv(transactions: &[Transaction]) -> Result<()> {
-    println!("hash,type,amount0,amount1,timestamp");
+/// Print transactions in CSV format; `detailed` appends block, participant, USD and token
+/// columns
+pub fn print_transactions_csv(transactions: &[Transaction], detailed: bool) -> Result<()> {
+    if !detailed {
+        println!("hash,type,amount0,amount1,timestamp");
+        for tx in transactions {
+            println!(
+                "{},{},{},{},{}",
+                escape_csv(&tx.hash),
+                escape_csv(tx.transaction_type.as_str()),
+                tx.amount0,
+                tx.amount1,
+                tx.timestamp
+            );
+        }
+        return Ok(());
+    }
+
+    println!(
+        "hash,type,amount0,amount1,timestamp,block_number,log_index,sender,recipient,value_usd,\
+         token0_symbol,token0_address,token1_symbol,token1_address"
+    );
+    let opt = |value: Option<String>| value.unwrap_or_default();
     for tx in transactions {
         println!(
-            "{},{},{},{},{}",
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
             escape_csv(&tx.hash),
-            escape_csv(&tx.transaction_type),
+            escape_csv(tx.transaction_type.as_str()),
             tx.amount0,
             tx.amount1,
-            tx.timestamp
+            tx.timestamp,
+            opt(tx.block_number.map(|b| b.to_string())),
+            opt(tx.log_index.map(|i| i.to_string())),
+            opt(tx.sender.as_ref().map(|a| a.as_str().to_string())),
+            opt(tx.recipient.as_ref().map(|a| a.as_str().to_string())),
+            opt(tx.value_usd.map(|v| v.to_string())),
+            opt(tx.token0.as_ref().map(|t| escape_csv(&t.symbol))),
+            opt(tx.token0.as_ref().map(|t| t.address.as_str().to_string())),
+            opt(tx.token1.as_ref().map(|t| escape_csv(&t.symbol))),
+            opt(tx.token1.as_ref().map(|t| t.address.as_str().to_string())),
         );
     }
     Ok(())
@@ -739,8 +801,189 @@ pub fn print_chain_stats_csv(chain_id: u32, stats: &ChainStats) -> Result<()> {
     Ok(())
 }
 
+/// Print cache statistics in table format
+pub fn print_cache_stats_table(stats: &CacheStats) -> Result<()> {
+    println!(
+        "{}",
+        color_bold(&format!("Response cache at {}", stats.dir.display()))
+    );
+    println!(
+        "{:<14} {:<10} {:<8} {:<8} {:<10}",
+        "Family", "TTL", "Entries", "Expired", "Size"
+    );
+    println!("{}", "-".repeat(54));
+
+    for family in &stats.families {
+        let ttl = if family.ttl_secs == 0 {
+            "off".to_string()
+        } else {
+            format!("{}s", family.ttl_secs)
+        };
+        println!(
+            "{:<14} {:<10} {:<8} {:<8} {:<10}",
+            family.family.as_str(),
+            ttl,
+            family.entries,
+            family.expired,
+            format_bytes(family.bytes)
+        );
+    }
+
+    println!("{}", "-".repeat(54));
+    println!(
+        "{:<14} {:<10} {:<8} {:<8} {:<10}",
+        "total",
+        "",
+        stats.total_entries,
+        stats.total_expired,
+        format_bytes(stats.total_bytes)
+    );
+    Ok(())
+}
+
+/// Print cache statistics in CSV format
+pub fn print_cache_stats_csv(stats: &CacheStats) -> Result<()> {
+    println!("family,ttl_secs,entries,expired,bytes");
+    for family in &stats.families {
+        println!(
+            "{},{},{},{},{}",
+            family.family.as_str(),
+            family.ttl_secs,
+            family.entries,
+            family.expired,
+            family.bytes
+        );
+    }
+    Ok(())
+}
+
+/// Print usage ledger summary in table format
+pub fn print_usage_table(rows: &[UsageSummaryRow], ledger_path: &Path) -> Result<()> {
+    if rows.is_empty() {
+        println!("No API calls recorded in {}", ledger_path.display());
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        color_bold(&format!("API usage from {}", ledger_path.display()))
+    );
+    println!(
+        "{:<12} {:<44} {:<10} {:<8} {:<10}",
+        "Period", "Endpoint", "Requests", "Errors", "Size"
+    );
+    println!("{}", "-".repeat(88));
+
+    for row in rows {
+        let errors = if row.errors > 0 {
+            color_red(&row.errors.to_string())
+        } else {
+            row.errors.to_string()
+        };
+        println!(
+            "{:<12} {:<44} {:<10} {:<8} {:<10}",
+            row.period,
+            truncate_string(&row.endpoint, 44),
+            row.requests,
+            errors,
+            format_bytes(row.bytes)
+        );
+    }
+
+    println!("{}", "-".repeat(88));
+    println!(
+        "{:<12} {:<44} {:<10} {:<8} {:<10}",
+        "total",
+        "",
+        rows.iter().map(|row| row.requests).sum::<u64>(),
+        rows.iter().map(|row| row.errors).sum::<u64>(),
+        format_bytes(rows.iter().map(|row| row.bytes).sum())
+    );
+    Ok(())
+}
+
+/// Print usage ledger summary in CSV format
+pub fn print_usage_csv(rows: &[UsageSummaryRow]) -> Result<()> {
+    println!("period,endpoint,requests,errors,bytes");
+    for row in rows {
+        println!(
+            "{},{},{},{},{}",
+            row.period, row.endpoint, row.requests, row.errors, row.bytes
+        );
+    }
+    Ok(())
+}
+
+/// Print a schema drift report as a table
+pub fn print_schema_report_table(report: &SchemaReport) -> Result<()> {
+    println!("{}", color_bold("Schema drift by model"));
+    println!(
+        "{:<22} {:<8} {:<30} {:<8}",
+        "Model", "Kind", "Field", "Records"
+    );
+    println!("{}", "-".repeat(72));
+
+    for (model, drift) in &report.models {
+        if drift.unknown_fields.is_empty() && drift.missing_fields.is_empty() {
+            println!(
+                "{:<22} {:<8} {:<30} {:<8}",
+                model,
+                color_green("ok"),
+                "",
+                drift.samples
+            );
+            continue;
+        }
+        for (field, count) in &drift.unknown_fields {
+            println!(
+                "{:<22} {:<8} {:<30} {}/{}",
+                model,
+                color_red("unknown"),
+                truncate_string(field, 30),
+                count,
+                drift.samples
+            );
+        }
+        for (field, count) in &drift.missing_fields {
+            println!(
+                "{:<22} {:<8} {:<30} {}/{}",
+                model,
+                "missing",
+                truncate_string(field, 30),
+                count,
+                drift.samples
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Print a schema drift report in CSV format
+pub fn print_schema_report_csv(report: &SchemaReport) -> Result<()> {
+    println!("model,kind,field,records,samples");
+    for (model, drift) in &report.models {
+        for (field, count) in &drift.unknown_fields {
+            println!("{},unknown,{},{},{}", model, escape_csv(field), count, drift.samples);
+        }
+        for (field, count) in &drift.missing_fields {
+            println!("{},missing,{},{},{}", model, field, count, drift.samples);
+        }
+    }
+    Ok(())
+}
+
 // Helper functions
 
+fn format_bytes(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
 fn print_pools_table_header() {
     println!(
         "{:<4} {:<20} {:<15} {:<12} {:<12} {:<8}",
@@ -773,10 +1016,7 @@ fn print_pool_table_row(index: usize, pool: &Pool) -> Result<()> {
 
 fn print_pool_summary(index: usize, pool: &Pool) -> Result<()> {
     println!("\n{}. {}", index, pool.display_name());
-    println!(
-        "   Address: {}",
-        address::format_address_default(&pool.address)
-    );
+    println!("   Address: {}", pool.address.short());
 
     if let Some(chain) = &pool.chain {
         println!("   Chain: {} (ID: {})", chain.name, chain.id);
@@ -805,10 +1045,7 @@ fn print_pool_summary(index: usize, pool: &Pool) -> Result<()> {
 
 fn print_position_summary(index: usize, position: &Position) -> Result<()> {
     println!("\n{}. Position {}", index, position.id);
-    println!(
-        "   Owner: {}",
-        address::format_address_default(&position.owner_address)
-    );
+    println!("   Owner: {}", position.owner_address.short());
     println!("   Status: {}", position.status);
     println!(
         "   Value: {}",
@@ -1,28 +1,61 @@
 // file: src/client.rs
-// description: Main API client implementation for the Krystal Cloud API, handling HTTP requests,
-//             authentication, response parsing, retry with exponential backoff, rate limiting,
-//             and providing high-level methods for API interaction
+// description: Main API client implementation for the Krystal Cloud API, handling HTTP requests
+//             through a pluggable transport, authentication, response parsing, retry with
+//             exponential backoff, rate limiting, and high-level methods for API interaction
 // docs_reference: https://docs.rs/reqwest/latest/reqwest/
 
+use crate::auth::{ApiKey, KeyPool};
+use crate::cache::{CacheConfig, DiskCache, MemoryCache, MemoryCacheConfig};
+use crate::cassette::{self, CassetteMode};
+use crate::envelope::{ChainEnvelope, ListEnvelope};
 use crate::error::{KrystalApiError, Result};
+use crate::middleware::{MiddlewareStack, ResponseInfo};
+use crate::position_id::PositionId;
 use crate::models::*;
 use crate::query::*;
+use crate::schema::{ParseMode, SchemaModel, SchemaReport};
+use crate::spec::{ApiSpec, SpecCheck};
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport, ReqwestTransport};
+use crate::usage::{UsageLedger, UsageRecord, UsageReport};
+use crate::utils::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+use crate::utils::pagination::PaginationIterator;
 use crate::utils::rate_limit::RateLimiter;
-use crate::utils::retry::{RetryConfig, retry_with_backoff};
-use log::debug;
-use reqwest::{Client, Response};
-use serde::de::DeserializeOwned;
+use crate::utils::retry::{RetryConfig, parse_retry_after, retry_with_backoff};
+use bytes::Bytes;
+use futures::future;
+use futures::stream::{self, Stream, StreamExt};
+use log::{debug, warn};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde::de::{DeserializeOwned, IgnoredAny};
 use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::env;
-use std::sync::Mutex;
-use std::time::Duration;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
 use url::Url;
 
+/// Outcome of an in-flight request, shared with coalesced callers
+type SharedResult = Arc<Result<Bytes>>;
+
+/// In-flight requests keyed by normalized URL
+type InflightMap = Mutex<HashMap<String, watch::Receiver<Option<SharedResult>>>>;
+
 /// Configuration for the API client
 #[derive(Debug, Clone)]
 pub struct ClientConfig {
-    /// Base URL for the API
+    /// Fallback API keys, tried in order after the primary key is rejected (401/402)
+    pub api_keys: Vec<ApiKey>,
+    /// Base URL for the API (plain HTTP only for loopback hosts unless `allow_insecure`)
     pub base_url: String,
+    /// Allow a plain-HTTP base URL on non-loopback hosts
+    pub allow_insecure: bool,
+    /// Proxy URL for all requests, e.g. `http://proxy.internal:3128`
+    pub proxy: Option<String>,
+    /// Extra PEM root certificate(s) to trust, e.g. for a TLS-intercepting proxy
+    pub ca_cert: Option<PathBuf>,
     /// Request timeout in seconds
     pub timeout_secs: u64,
     /// User agent string
@@ -31,33 +64,160 @@ pub struct ClientConfig {
     pub retry: RetryConfig,
     /// Maximum requests per second (0 = unlimited)
     pub max_requests_per_second: usize,
+    /// Record or replay API traffic to/from a cassette directory
+    pub cassette: Option<CassetteMode>,
+    /// Persistent on-disk response cache (disabled when `None` or a cassette is set)
+    pub cache: Option<CacheConfig>,
+    /// Short-lived in-memory memoization of responses (disabled when `None` or a cassette
+    /// is set)
+    pub memory_cache: Option<MemoryCacheConfig>,
+    /// Share one HTTP request between concurrent identical GETs
+    pub coalesce_requests: bool,
+    /// Append every request to a usage ledger file (not written in replay mode)
+    pub usage_ledger: Option<PathBuf>,
+    /// Hooks run before each HTTP request and after each response, in order
+    pub middleware: MiddlewareStack,
+    /// Fail fast after sustained retryable failures (disabled when `None`)
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Keep unknown response fields (lenient) or reject them (strict)
+    pub parse_mode: ParseMode,
+    /// Check typed requests against the bundled `api.json` before sending them
+    pub spec_check: SpecCheck,
 }
 
 impl Default for ClientConfig {
     fn default() -> Self {
         Self {
+            api_keys: Vec::new(),
             base_url: "https://cloud-api.krystal.app".to_string(),
+            allow_insecure: false,
+            proxy: None,
+            ca_cert: None,
             timeout_secs: 30,
             user_agent: format!("krystal-rust-client/{}", env!("CARGO_PKG_VERSION")),
             retry: RetryConfig::default(),
             max_requests_per_second: 10,
+            cassette: None,
+            cache: None,
+            memory_cache: None,
+            coalesce_requests: true,
+            usage_ledger: None,
+            middleware: MiddlewareStack::new(),
+            circuit_breaker: None,
+            parse_mode: ParseMode::Lenient,
+            spec_check: SpecCheck::Off,
         }
     }
 }
 
+impl ClientConfig {
+    /// Parse the base URL, rejecting plain HTTP unless the host is loopback or
+    /// `allow_insecure` is set
+    pub fn checked_base_url(&self) -> Result<Url> {
+        let url = Url::parse(&self.base_url)?;
+        match url.scheme() {
+            "https" => Ok(url),
+            "http" if self.allow_insecure || is_loopback(&url) => Ok(url),
+            "http" => Err(KrystalApiError::InvalidParams(format!(
+                "Refusing plain HTTP base URL {url}; use https, a loopback host, or allow_insecure"
+            ))),
+            other => Err(KrystalApiError::InvalidParams(format!(
+                "Unsupported base URL scheme '{other}'"
+            ))),
+        }
+    }
+}
+
+fn is_loopback(url: &Url) -> bool {
+    match url.host() {
+        Some(url::Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    }
+}
+
+/// Per-call overrides accepted by the `_with_options` client methods.
+///
+/// The cancellation token and deadline bound the whole call, including retries, backoff
+/// waits and every page of multi-request methods. `timeout` applies to each HTTP attempt.
+/// Callers waiting on a coalesced request keep their own cancellation and deadline but
+/// share the leading caller's timeout and retry settings.
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Timeout for each HTTP attempt, overriding `ClientConfig::timeout_secs`
+    pub timeout: Option<Duration>,
+    /// Give up with `DeadlineExceeded` once this instant passes
+    pub deadline: Option<Instant>,
+    /// Give up with `Cancelled` as soon as this token is cancelled
+    pub cancel: Option<CancellationToken>,
+    /// Retry policy overriding `ClientConfig::retry`
+    pub retry: Option<RetryConfig>,
+    /// Skip cached responses and request coalescing (the fresh response is still cached)
+    pub bypass_cache: bool,
+}
+
+impl RequestOptions {
+    /// Options that change nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the per-attempt timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set an absolute deadline for the whole call
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Set a deadline relative to now
+    pub fn deadline_in(self, budget: Duration) -> Self {
+        self.deadline(Instant::now() + budget)
+    }
+
+    /// Cancel the call when this token is cancelled
+    pub fn cancel_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Override the retry policy
+    pub fn retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Skip cached responses
+    pub fn bypass_cache(mut self, bypass: bool) -> Self {
+        self.bypass_cache = bypass;
+        self
+    }
+}
+
 /// Main API client for interacting with the Krystal Cloud API
 pub struct KrystalApiClient {
-    client: Client,
+    transport: Arc<dyn HttpTransport>,
     config: ClientConfig,
-    api_key: String,
+    keys: KeyPool,
     rate_limiter: Mutex<RateLimiter>,
+    breaker: Option<Mutex<CircuitBreaker>>,
+    disk_cache: Option<DiskCache>,
+    memory_cache: Option<Mutex<MemoryCache>>,
+    inflight: InflightMap,
+    usage: Mutex<UsageReport>,
+    ledger: Option<UsageLedger>,
 }
 
 impl std::fmt::Debug for KrystalApiClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("KrystalApiClient")
             .field("config", &self.config)
-            .field("api_key", &"[redacted]")
+            .field("api_keys", &self.keys)
             .finish()
     }
 }
@@ -65,29 +225,71 @@ impl std::fmt::Debug for KrystalApiClient {
 impl KrystalApiClient {
     /// Create a new API client with custom configuration
     pub fn with_config(api_key: String, config: ClientConfig) -> Result<Self> {
-        let api_key = api_key.trim().to_string();
-        if api_key.is_empty() {
+        let transport = ReqwestTransport::new(&config)?;
+        Self::with_transport(api_key, config, Arc::new(transport))
+    }
+
+    /// Create a new API client that sends requests through a custom transport
+    pub fn with_transport(
+        api_key: String,
+        config: ClientConfig,
+        transport: Arc<dyn HttpTransport>,
+    ) -> Result<Self> {
+        let keys = KeyPool::new(
+            std::iter::once(ApiKey::new(api_key)).chain(config.api_keys.iter().cloned()),
+        );
+        if keys.is_empty() {
             return Err(KrystalApiError::AuthError);
         }
 
-        Url::parse(&config.base_url)?;
+        config.checked_base_url()?;
 
-        let client = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_secs))
-            .user_agent(&config.user_agent)
-            .https_only(true)
-            .build()?;
+        let transport = match &config.cassette {
+            Some(mode) => cassette::wrap_transport(transport, mode)?,
+            None => transport,
+        };
 
         let rate_limiter = RateLimiter::new(
             config.max_requests_per_second,
             Duration::from_secs(1),
         );
 
+        let breaker = config
+            .circuit_breaker
+            .clone()
+            .map(|breaker| Mutex::new(CircuitBreaker::new(breaker)));
+        // Caches sit in front of the transport: with a cassette they would hide requests from
+        // the recorder, or serve live responses (and store replayed ones) during replay
+        let use_cache = config.cassette.is_none();
+        let disk_cache = config
+            .cache
+            .clone()
+            .filter(|_| use_cache)
+            .map(DiskCache::new);
+        let memory_cache = config
+            .memory_cache
+            .clone()
+            .filter(|_| use_cache)
+            .map(|memo| Mutex::new(MemoryCache::new(memo)));
+
+        let replaying = matches!(config.cassette, Some(CassetteMode::Replay(_)));
+        let ledger = config
+            .usage_ledger
+            .clone()
+            .filter(|_| !replaying)
+            .map(UsageLedger::new);
+
         Ok(Self {
-            client,
+            transport,
             config,
-            api_key,
+            keys,
             rate_limiter: Mutex::new(rate_limiter),
+            breaker,
+            disk_cache,
+            memory_cache,
+            inflight: Mutex::new(HashMap::new()),
+            usage: Mutex::new(UsageReport::new()),
+            ledger,
         })
     }
 
@@ -102,34 +304,78 @@ impl KrystalApiClient {
         Self::new(api_key)
     }
 
-    /// Handle API response and convert to appropriate error types
-    async fn handle_response(response: Response) -> Result<Value> {
-        let status = response.status();
-        let url = response.url().to_string();
+    /// Configuration this client was built with
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Request counts, sizes, latencies and status codes per endpoint since creation
+    pub fn usage_report(&self) -> UsageReport {
+        self.usage.lock().expect("usage report poisoned").clone()
+    }
+
+    /// Reset the in-process usage counters (the ledger file is untouched)
+    pub fn reset_usage(&self) {
+        *self.usage.lock().expect("usage report poisoned") = UsageReport::new();
+    }
+
+    /// Fingerprint of the API key currently used for requests (safe to log)
+    pub fn active_key_fingerprint(&self) -> String {
+        self.keys.current().1.fingerprint()
+    }
 
-        debug!("Response: {} from {}", status.as_u16(), url);
+    /// Number of API keys available for failover
+    pub fn key_count(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Return to the primary API key after failover (e.g. once its account is topped up)
+    pub fn reset_key_rotation(&self) {
+        self.keys.reset();
+    }
 
-        match status.as_u16() {
-            200..=299 => response
-                .json::<Value>()
-                .await
+    /// Circuit breaker state for health checks (`None` when the breaker is disabled)
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.breaker
+            .as_ref()
+            .map(|breaker| breaker.lock().expect("circuit breaker poisoned").state())
+    }
+
+    /// Drop all memoized responses
+    pub fn clear_memory_cache(&self) {
+        if let Some(memo) = &self.memory_cache {
+            memo.lock().expect("memory cache poisoned").clear();
+        }
+    }
+
+    /// Handle API response and convert to appropriate error types; a successful body is
+    /// checked to be well-formed JSON but kept as raw bytes
+    fn handle_response(response: HttpResponse, url: &Url) -> Result<Bytes> {
+        let status = response.status;
+
+        debug!("Response: {} from {}", status, url);
+
+        match status {
+            200..=299 => serde_json::from_slice::<IgnoredAny>(&response.body)
+                .map(|_| response.body)
                 .map_err(KrystalApiError::from),
-            400 => {
-                let error_body = response.text().await.unwrap_or_default();
-                Err(KrystalApiError::InvalidParams(format!(
-                    "Bad request: {}",
-                    error_body
-                )))
-            }
+            400 => Err(KrystalApiError::InvalidParams(format!(
+                "Bad request: {}",
+                response.text()
+            ))),
             401 => Err(KrystalApiError::AuthError),
             402 => Err(KrystalApiError::PaymentRequired),
-            _ => {
-                let error_text = response.text().await.unwrap_or_default();
-                Err(KrystalApiError::ApiError {
-                    status: status.as_u16(),
-                    message: error_text,
-                })
-            }
+            429 => Err(KrystalApiError::RateLimited {
+                retry_after: response
+                    .headers
+                    .get("retry-after")
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(parse_retry_after),
+            }),
+            _ => Err(KrystalApiError::ApiError {
+                status,
+                message: response.text(),
+            }),
         }
     }
 
@@ -143,23 +389,63 @@ impl KrystalApiClient {
         Ok(base.join(path)?)
     }
 
-    fn parse_item<T: DeserializeOwned>(json: Value, context: &str) -> Result<T> {
-        serde_json::from_value(json).map_err(|e| {
-            KrystalApiError::InvalidParams(format!("Failed to parse {context} response: {e}"))
-        })
+    /// Parse a model from a response body
+    fn parse_item<T: DeserializeOwned + SchemaModel>(
+        &self,
+        body: &[u8],
+        context: &str,
+    ) -> Result<T> {
+        let item: T = serde_json::from_slice(body).map_err(|e| parse_error(context, e))?;
+        self.check_schema(std::slice::from_ref(&item), context)?;
+        Ok(item)
     }
 
-    fn parse_items<T: DeserializeOwned>(items: &[Value], context: &str) -> Result<Vec<T>> {
-        items
-            .iter()
-            .cloned()
-            .map(|item| Self::parse_item(item, context))
-            .collect()
+    /// Parse a list response, either a bare array or an object wrapping it under `key`
+    fn parse_list<T: DeserializeOwned + SchemaModel>(
+        &self,
+        body: &[u8],
+        key: &str,
+        context: &str,
+    ) -> Result<ListEnvelope<T>> {
+        let envelope: ListEnvelope<T> =
+            ListEnvelope::from_slice(body, key).map_err(|e| parse_error(context, e))?;
+        if let Some(items) = &envelope.items {
+            self.check_schema(items, context)?;
+        }
+        Ok(envelope)
+    }
+
+    /// In strict mode, fail when parsed models carry unknown fields
+    fn check_schema<T: SchemaModel>(&self, items: &[T], context: &str) -> Result<()> {
+        if self.config.parse_mode == ParseMode::Lenient {
+            return Ok(());
+        }
+
+        let mut report = SchemaReport::new();
+        report.observe_all(items);
+        if report.has_unknown_fields() {
+            return Err(KrystalApiError::SchemaDrift {
+                context: context.to_string(),
+                fields: report.unknown_fields(),
+            });
+        }
+        Ok(())
     }
 
     /// Create a GET request with authentication headers
-    fn authenticated_get(&self, url: Url) -> reqwest::RequestBuilder {
-        self.client.get(url).header("KC-APIKey", &self.api_key)
+    fn authenticated_get(&self, url: Url, key: &ApiKey) -> Result<HttpRequest> {
+        let mut api_key =
+            HeaderValue::from_str(key.expose()).map_err(|_| KrystalApiError::AuthError)?;
+        api_key.set_sensitive(true);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("KC-APIKey", api_key);
+
+        Ok(HttpRequest {
+            url,
+            headers,
+            timeout: None,
+        })
     }
 
     /// Enforce rate limit, waiting if necessary
@@ -182,59 +468,405 @@ impl KrystalApiClient {
         }
     }
 
-    /// Execute a GET request with rate limiting and retry
-    async fn get_with_retry(&self, url: Url) -> Result<Value> {
-        let config = self.config.retry.clone();
+    /// Check a request URL against the bundled API spec according to `spec_check`
+    fn check_spec(&self, url: &Url) -> Result<()> {
+        if self.config.spec_check == SpecCheck::Off {
+            return Ok(());
+        }
+
+        let violations = ApiSpec::bundled().check_url(url, &self.endpoint("")?);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let violations: Vec<String> = violations.iter().map(ToString::to_string).collect();
+        if self.config.spec_check == SpecCheck::Warn {
+            warn!("Request {} does not match the API spec: {}", url, violations.join("; "));
+            return Ok(());
+        }
+        Err(KrystalApiError::SpecViolation {
+            path: url.path().to_string(),
+            violations,
+        })
+    }
+
+    /// Execute a typed GET request after checking it against the API spec
+    async fn get_with_retry(&self, url: Url, options: &RequestOptions) -> Result<Bytes> {
+        self.check_spec(&url)?;
+        self.get_unchecked(url, options).await
+    }
+
+    /// Execute a GET request with caching, coalescing, rate limiting and retry, bounded by
+    /// the options' cancellation token and deadline
+    async fn get_unchecked(&self, url: Url, options: &RequestOptions) -> Result<Bytes> {
+        let deadline = options.deadline.map(tokio::time::Instant::from_std);
+        let cancelled = async {
+            match &options.cancel {
+                Some(token) => token.cancelled().await,
+                None => future::pending().await,
+            }
+        };
+        let expired = async {
+            match deadline {
+                Some(deadline) => tokio::time::sleep_until(deadline).await,
+                None => future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            biased;
+            _ = cancelled => Err(KrystalApiError::Cancelled),
+            _ = expired => Err(KrystalApiError::DeadlineExceeded),
+            result = self.get_cached_or_fetch(url, options) => result,
+        }
+    }
+
+    async fn get_cached_or_fetch(&self, url: Url, options: &RequestOptions) -> Result<Bytes> {
+        let key = DiskCache::cache_key(&url);
+
+        if options.bypass_cache {
+            return self.fetch_and_store(&url, &key, options).await;
+        }
+
+        // `--refresh` skips reads from both layers; fresh responses are still stored
+        let refresh = self.config.cache.as_ref().is_some_and(|cache| cache.refresh);
+        if !refresh
+            && let Some(memo) = &self.memory_cache
+            && let Some(body) = memo.lock().expect("memory cache poisoned").get(&key)
+        {
+            debug!("Memory cache hit for {}", url);
+            return Ok(body);
+        }
+
+        if let Some(cache) = &self.disk_cache
+            && let Some(body) = cache.get(&url)
+        {
+            debug!("Cache hit for {}", url);
+            self.memoize(&key, &body);
+            return Ok(body);
+        }
+
+        if self.config.coalesce_requests {
+            self.fetch_coalesced(&url, key, options).await
+        } else {
+            self.fetch_and_store(&url, &key, options).await
+        }
+    }
+
+    /// Fetch a URL, sharing the result with concurrent callers for the same key.
+    ///
+    /// The first caller becomes the leader and performs the request; later callers wait
+    /// for its result. If the leader is cancelled before finishing, waiting callers fall
+    /// back to fetching on their own.
+    async fn fetch_coalesced(
+        &self,
+        url: &Url,
+        key: String,
+        options: &RequestOptions,
+    ) -> Result<Bytes> {
+        let (sender, receiver) = watch::channel(None);
+        let existing = {
+            let mut inflight = self.inflight.lock().expect("inflight map poisoned");
+            match inflight.get(&key) {
+                Some(existing) => Some(existing.clone()),
+                None => {
+                    inflight.insert(key.clone(), receiver);
+                    None
+                }
+            }
+        };
+
+        let Some(mut receiver) = existing else {
+            let leader = InflightLeader {
+                inflight: &self.inflight,
+                key,
+                sender: Some(sender),
+            };
+            let result = self.fetch_and_store(url, &leader.key, options).await;
+            leader.complete(&result);
+            return result;
+        };
+
+        debug!("Coalescing request for {}", url);
+        let shared = receiver
+            .wait_for(Option::is_some)
+            .await
+            .ok()
+            .and_then(|value| value.clone());
+
+        match shared.as_deref() {
+            Some(Ok(body)) => Ok(body.clone()),
+            Some(Err(e)) => Err(e.duplicate()),
+            None => self.fetch_and_store(url, &key, options).await,
+        }
+    }
+
+    /// Fetch a URL and populate the configured caches
+    async fn fetch_and_store(
+        &self,
+        url: &Url,
+        key: &str,
+        options: &RequestOptions,
+    ) -> Result<Bytes> {
+        let body = self.fetch_with_retry(url, options).await?;
+
+        if let Some(cache) = &self.disk_cache
+            && let Err(e) = cache.put(url, &body)
+        {
+            debug!("Failed to write cache entry for {}: {}", url, e);
+        }
+        self.memoize(key, &body);
+
+        Ok(body)
+    }
+
+    fn memoize(&self, key: &str, body: &Bytes) {
+        if let Some(memo) = &self.memory_cache {
+            memo.lock()
+                .expect("memory cache poisoned")
+                .insert(key.to_string(), body.clone());
+        }
+    }
+
+    /// Execute a GET request against the transport with rate limiting and retry.
+    ///
+    /// A 401 or 402 moves to the next API key and retries immediately without using up
+    /// a retry attempt; the error is returned once every key has been rejected.
+    async fn fetch_with_retry(&self, url: &Url, options: &RequestOptions) -> Result<Bytes> {
+        let config = options
+            .retry
+            .clone()
+            .unwrap_or_else(|| self.config.retry.clone());
         retry_with_backoff(config, || async {
-            self.enforce_rate_limit().await;
-            let url = url.clone();
-            debug!("GET {}", url);
-            let response = self.authenticated_get(url).send().await?;
-            Self::handle_response(response).await
+            loop {
+                self.acquire_circuit()?;
+                self.enforce_rate_limit().await;
+                let (index, key) = self.keys.current();
+                debug!("GET {} (key {})", url, key.fingerprint());
+                let mut request = self.authenticated_get(url.clone(), key)?;
+                request.timeout = options.timeout;
+                self.config.middleware.before_request(&mut request);
+                let sent_url = request.url.clone();
+                let started = Instant::now();
+                let outcome = match options.timeout {
+                    Some(limit) => tokio::time::timeout(limit, self.transport.get(request))
+                        .await
+                        .unwrap_or(Err(KrystalApiError::Timeout(limit))),
+                    None => self.transport.get(request).await,
+                };
+                let latency = started.elapsed();
+                self.record_usage(url, key, &outcome, latency);
+
+                let status = outcome.as_ref().ok().map(|response| response.status);
+                let result = outcome.and_then(|response| Self::handle_response(response, url));
+                self.config.middleware.after_response(&ResponseInfo {
+                    url: &sent_url,
+                    status,
+                    latency,
+                    result: &result,
+                });
+                self.record_circuit(&result);
+
+                match result {
+                    Err(e)
+                        if (e.is_auth_error() || e.requires_payment())
+                            && self.keys.rotate_from(index) =>
+                    {
+                        debug!(
+                            "Key {} rejected ({}), failing over to key {}",
+                            key.fingerprint(),
+                            e,
+                            self.active_key_fingerprint()
+                        );
+                    }
+                    result => return result,
+                }
+            }
         })
         .await
     }
 
+    /// Fail fast with `CircuitOpen` while the circuit breaker is open
+    fn acquire_circuit(&self) -> Result<()> {
+        let Some(breaker) = &self.breaker else {
+            return Ok(());
+        };
+        breaker
+            .lock()
+            .expect("circuit breaker poisoned")
+            .try_acquire()
+            .map_err(|retry_in| KrystalApiError::CircuitOpen { retry_in })
+    }
+
+    /// Feed an attempt's outcome to the circuit breaker; only retryable errors count as failures
+    fn record_circuit(&self, result: &Result<Bytes>) {
+        let Some(breaker) = &self.breaker else {
+            return;
+        };
+        let mut breaker = breaker.lock().expect("circuit breaker poisoned");
+        match result {
+            Err(e) if e.is_retryable() => {
+                breaker.record_failure();
+                if breaker.state() == CircuitState::Open {
+                    let failures = breaker.consecutive_failures();
+                    debug!("Circuit breaker open after {} failures", failures);
+                }
+            }
+            _ => breaker.record_success(),
+        }
+    }
+
+    /// Count a request in the usage report and ledger
+    fn record_usage(
+        &self,
+        url: &Url,
+        key: &ApiKey,
+        outcome: &Result<HttpResponse>,
+        latency: Duration,
+    ) {
+        let (status, bytes) = match outcome {
+            Ok(response) => (response.status, response.body.len()),
+            Err(_) => (0, 0),
+        };
+        let record = UsageRecord::new(url, status, bytes, latency).with_key(key.fingerprint());
+
+        self.usage
+            .lock()
+            .expect("usage report poisoned")
+            .record(&record);
+
+        if let Some(ledger) = &self.ledger
+            && let Err(e) = ledger.append(&record)
+        {
+            debug!(
+                "Failed to append to usage ledger {}: {}",
+                ledger.path().display(),
+                e
+            );
+        }
+    }
+
     /// Get list of all supported blockchain networks
     pub async fn get_chains(&self) -> Result<Vec<ChainInfo>> {
+        self.get_chains_with_options(&RequestOptions::default()).await
+    }
+
+    /// [`get_chains`](Self::get_chains) with [`RequestOptions`]
+    pub async fn get_chains_with_options(
+        &self,
+        options: &RequestOptions,
+    ) -> Result<Vec<ChainInfo>> {
         let url = self.endpoint("v1/chains")?;
-        let json = self.get_with_retry(url).await?;
+        let body = self.get_with_retry(url, options).await?;
 
-        let chains_data = json
-            .as_array()
-            .or_else(|| json.get("chains").and_then(|c| c.as_array()))
+        self.parse_list(&body, "chains", "chains")?
+            .items
             .ok_or_else(|| {
                 KrystalApiError::InvalidParams("Invalid chains response format".to_string())
-            })?;
-
-        Self::parse_items(chains_data, "chains")
+            })
     }
 
     /// Get stats for a specific chain
     pub async fn get_chain_stats(&self, chain_id: u32) -> Result<ChainStats> {
+        self.get_chain_stats_with_options(chain_id, &RequestOptions::default()).await
+    }
+
+    /// [`get_chain_stats`](Self::get_chain_stats) with [`RequestOptions`]
+    pub async fn get_chain_stats_with_options(
+        &self,
+        chain_id: u32,
+        options: &RequestOptions,
+    ) -> Result<ChainStats> {
         let url = self.endpoint(&format!("v1/chains/{chain_id}"))?;
-        let json = self.get_with_retry(url).await?;
-        let payload = json.get("chain").cloned().unwrap_or(json);
-        Self::parse_item(payload, "chain stats")
+        let body = self.get_with_retry(url, options).await?;
+        let envelope: ChainEnvelope<'_> =
+            serde_json::from_slice(&body).map_err(|e| parse_error("chain stats", e))?;
+        match envelope.chain {
+            Some(chain) => self.parse_item(chain.get().as_bytes(), "chain stats"),
+            None => self.parse_item(&body, "chain stats"),
+        }
     }
 
-    /// Get pool data with filtering options
+    /// Get pool data with filtering options.
+    ///
+    /// Queries with `chain_ids` send one request per chain concurrently, then merge the
+    /// pools, re-sort them by `sort_by` (keeping chain order when unset) and apply
+    /// `offset` and `limit` across the merged list. Chains whose request fails are logged and
+    /// left out; the query only fails when every chain does.
     pub async fn get_pools(&self, query: PoolsQuery) -> Result<Vec<Pool>> {
+        self.get_pools_with_options(query, &RequestOptions::default()).await
+    }
+
+    /// [`get_pools`](Self::get_pools) with [`RequestOptions`]
+    pub async fn get_pools_with_options(
+        &self,
+        query: PoolsQuery,
+        options: &RequestOptions,
+    ) -> Result<Vec<Pool>> {
         query.validate().map_err(KrystalApiError::InvalidParams)?;
 
-        let mut url = self.endpoint("v1/pools")?;
-        self.build_pools_query_params(&mut url, &query);
+        if query.is_multi_chain() {
+            return self.get_pools_multi_chain(query, options).await;
+        }
+        self.fetch_pools(&query, options).await
+    }
+
+    /// Fan a pools query out to every chain in `chain_ids` and merge the chains that succeed
+    async fn get_pools_multi_chain(
+        &self,
+        query: PoolsQuery,
+        options: &RequestOptions,
+    ) -> Result<Vec<Pool>> {
+        let offset = query.offset.unwrap_or(0);
+        // Each chain must return enough pools to fill the global page on its own
+        let per_chain_limit = query
+            .limit
+            .map(|limit| (limit + offset).min(MAX_POOLS_PAGE_SIZE));
+
+        let requests = query.chain_ids.iter().map(|&chain_id| {
+            let chain_query = PoolsQuery {
+                chain_id: Some(chain_id),
+                chain_ids: Vec::new(),
+                limit: per_chain_limit,
+                offset: None,
+                ..query.clone()
+            };
+            async move { (chain_id, self.fetch_pools(&chain_query, options).await) }
+        });
+
+        let mut pools = Vec::new();
+        let mut succeeded = 0;
+        let mut last_error = None;
+        for (chain_id, result) in future::join_all(requests).await {
+            match result {
+                Ok(chain_pools) => {
+                    succeeded += 1;
+                    pools.extend(chain_pools);
+                }
+                Err(e) => {
+                    warn!("Skipping chain {} in multi-chain pools query: {}", chain_id, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        if let Some(e) = last_error.filter(|_| succeeded == 0) {
+            return Err(e);
+        }
 
-        let json = self.get_with_retry(url).await?;
+        if let Some(sort_by) = query.sort_by {
+            pools.sort_by(|a, b| b.sort_value(sort_by).total_cmp(&a.sort_value(sort_by)));
+        }
 
-        let pools_data = json
-            .get("pools")
-            .and_then(|p| p.as_array())
-            .or_else(|| json.as_array())
-            .map(Vec::as_slice)
-            .unwrap_or(&[]);
+        let limit = query.limit.map_or(usize::MAX, |l| l as usize);
+        Ok(pools.into_iter().skip(offset as usize).take(limit).collect())
+    }
 
-        Self::parse_items(pools_data, "pools")
+    /// Fetch a single page of pools for a single-chain (or chain-agnostic) query
+    async fn fetch_pools(&self, query: &PoolsQuery, options: &RequestOptions) -> Result<Vec<Pool>> {
+        let mut url = self.endpoint("v1/pools")?;
+        self.build_pools_query_params(&mut url, query);
+
+        let body = self.get_with_retry(url, options).await?;
+        Ok(self.parse_list(&body, "pools", "pools")?.into_items())
     }
 
     /// Helper method to build query parameters for pools
@@ -280,6 +912,25 @@ impl KrystalApiClient {
         pool_address: &str,
         factory_address: Option<&str>,
         with_incentives: bool,
+    ) -> Result<Pool> {
+        self.get_pool_detail_with_options(
+            chain_id,
+            pool_address,
+            factory_address,
+            with_incentives,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// [`get_pool_detail`](Self::get_pool_detail) with [`RequestOptions`]
+    pub async fn get_pool_detail_with_options(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        with_incentives: bool,
+        options: &RequestOptions,
     ) -> Result<Pool> {
         let mut url = self.endpoint(&format!("v1/pools/{chain_id}/{pool_address}"))?;
 
@@ -291,8 +942,8 @@ impl KrystalApiClient {
             query_pairs.append_pair("withIncentives", &with_incentives.to_string());
         }
 
-        let json = self.get_with_retry(url).await?;
-        Self::parse_item(json, "pool detail")
+        let body = self.get_with_retry(url, options).await?;
+        self.parse_item(&body, "pool detail")
     }
 
     /// Get historical data for a specific pool
@@ -302,6 +953,25 @@ impl KrystalApiClient {
         pool_address: &str,
         factory_address: Option<&str>,
         query: Option<TransactionQuery>,
+    ) -> Result<PoolHistoricalData> {
+        self.get_pool_historical_with_options(
+            chain_id,
+            pool_address,
+            factory_address,
+            query,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// [`get_pool_historical`](Self::get_pool_historical) with [`RequestOptions`]
+    pub async fn get_pool_historical_with_options(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        query: Option<TransactionQuery>,
+        options: &RequestOptions,
     ) -> Result<PoolHistoricalData> {
         let mut url = self.endpoint(&format!("v1/pools/{chain_id}/{pool_address}/historical"))?;
 
@@ -322,8 +992,8 @@ impl KrystalApiClient {
             }
         }
 
-        let json = self.get_with_retry(url).await?;
-        Self::parse_item(json, "pool historical")
+        let body = self.get_with_retry(url, options).await?;
+        self.parse_item(&body, "pool historical")
     }
 
     /// Get transactions for a specific pool
@@ -333,6 +1003,25 @@ impl KrystalApiClient {
         pool_address: &str,
         factory_address: Option<&str>,
         query: Option<TransactionQuery>,
+    ) -> Result<Vec<Transaction>> {
+        self.get_pool_transactions_with_options(
+            chain_id,
+            pool_address,
+            factory_address,
+            query,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// [`get_pool_transactions`](Self::get_pool_transactions) with [`RequestOptions`]
+    pub async fn get_pool_transactions_with_options(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        query: Option<TransactionQuery>,
+        options: &RequestOptions,
     ) -> Result<Vec<Transaction>> {
         if let Some(q) = &query {
             q.validate().map_err(KrystalApiError::InvalidParams)?;
@@ -363,19 +1052,21 @@ impl KrystalApiClient {
             }
         }
 
-        let json = self.get_with_retry(url).await?;
-
-        let txs_data = json
-            .get("transactions")
-            .and_then(|t| t.as_array())
-            .map(Vec::as_slice)
-            .unwrap_or(&[]);
-
-        Self::parse_items(txs_data, "pool transactions")
+        let body = self.get_with_retry(url, options).await?;
+        Ok(self.parse_list(&body, "transactions", "pool transactions")?.into_items())
     }
 
     /// Get all positions for a wallet
     pub async fn get_positions(&self, query: PositionsQuery) -> Result<Vec<Position>> {
+        self.get_positions_with_options(query, &RequestOptions::default()).await
+    }
+
+    /// [`get_positions`](Self::get_positions) with [`RequestOptions`]
+    pub async fn get_positions_with_options(
+        &self,
+        query: PositionsQuery,
+        options: &RequestOptions,
+    ) -> Result<Vec<Position>> {
         query.validate().map_err(KrystalApiError::InvalidParams)?;
 
         let mut url = self.endpoint("v1/positions")?;
@@ -399,23 +1090,34 @@ impl KrystalApiClient {
             }
         }
 
-        let json = self.get_with_retry(url).await?;
-
-        let positions_data = json
-            .get("positions")
-            .and_then(|p| p.as_array())
-            .or_else(|| json.as_array())
-            .map(Vec::as_slice)
-            .unwrap_or(&[]);
-
-        Self::parse_items(positions_data, "positions")
+        let body = self.get_with_retry(url, options).await?;
+        Ok(self.parse_list(&body, "positions", "positions")?.into_items())
     }
 
     /// Get detailed information about a specific position
-    pub async fn get_position_detail(&self, chain_id: u32, position_id: &str) -> Result<Position> {
+    pub async fn get_position_detail(
+        &self,
+        chain_id: u32,
+        position_id: &PositionId,
+    ) -> Result<Position> {
+        self.get_position_detail_with_options(
+            chain_id,
+            position_id,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// [`get_position_detail`](Self::get_position_detail) with [`RequestOptions`]
+    pub async fn get_position_detail_with_options(
+        &self,
+        chain_id: u32,
+        position_id: &PositionId,
+        options: &RequestOptions,
+    ) -> Result<Position> {
         let url = self.endpoint(&format!("v1/positions/{chain_id}/{position_id}"))?;
-        let json = self.get_with_retry(url).await?;
-        Self::parse_item(json, "position detail")
+        let body = self.get_with_retry(url, options).await?;
+        self.parse_item(&body, "position detail")
     }
 
     /// Get transaction history for a specific position
@@ -426,6 +1128,27 @@ impl KrystalApiClient {
         token_address: &str,
         token_id: Option<&str>,
         query: Option<TransactionQuery>,
+    ) -> Result<Vec<Transaction>> {
+        self.get_position_transactions_with_options(
+            chain_id,
+            wallet,
+            token_address,
+            token_id,
+            query,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// [`get_position_transactions`](Self::get_position_transactions) with [`RequestOptions`]
+    pub async fn get_position_transactions_with_options(
+        &self,
+        chain_id: u32,
+        wallet: Option<&str>,
+        token_address: &str,
+        token_id: Option<&str>,
+        query: Option<TransactionQuery>,
+        options: &RequestOptions,
     ) -> Result<Vec<Transaction>> {
         if let Some(q) = &query {
             q.validate().map_err(KrystalApiError::InvalidParams)?;
@@ -457,29 +1180,56 @@ impl KrystalApiClient {
             }
         }
 
-        let json = self.get_with_retry(url).await?;
-
-        let txs_data = json
-            .get("transactions")
-            .and_then(|t| t.as_array())
-            .map(Vec::as_slice)
-            .unwrap_or(&[]);
-
-        Self::parse_items(txs_data, "position transactions")
+        let body = self.get_with_retry(url, options).await?;
+        Ok(self.parse_list(&body, "transactions", "position transactions")?.into_items())
     }
 
     /// Get list of all supported protocols
     pub async fn get_protocols(&self) -> Result<Vec<ProtocolSummary>> {
+        self.get_protocols_with_options(&RequestOptions::default()).await
+    }
+
+    /// [`get_protocols`](Self::get_protocols) with [`RequestOptions`]
+    pub async fn get_protocols_with_options(
+        &self,
+        options: &RequestOptions,
+    ) -> Result<Vec<ProtocolSummary>> {
         let url = self.endpoint("v1/protocols")?;
-        let json = self.get_with_retry(url).await?;
+        let body = self.get_with_retry(url, options).await?;
+        Ok(self.parse_list(&body, "protocols", "protocols")?.into_items())
+    }
 
-        let protocols_data = json
-            .as_array()
-            .or_else(|| json.get("protocols").and_then(|p| p.as_array()))
-            .map(Vec::as_slice)
-            .unwrap_or(&[]);
+    /// Call any API path relative to the base URL, e.g. `v1/pools/1/0xabc/fees`, and return
+    /// the JSON body unparsed.
+    ///
+    /// Intended for endpoints and parameters this crate does not model yet; requests go
+    /// through the same authentication, caching, rate limiting and retry as typed calls,
+    /// but skip the spec check.
+    /// Parameters are appended in order, so repeated keys are preserved.
+    pub async fn get_raw(&self, path: &str, params: &[(&str, &str)]) -> Result<Value> {
+        self.get_raw_with_options(path, params, &RequestOptions::default()).await
+    }
 
-        Self::parse_items(protocols_data, "protocols")
+    /// [`get_raw`](Self::get_raw) with [`RequestOptions`]
+    pub async fn get_raw_with_options(
+        &self,
+        path: &str,
+        params: &[(&str, &str)],
+        options: &RequestOptions,
+    ) -> Result<Value> {
+        let mut url = self.endpoint(path.trim_start_matches('/'))?;
+        let base = self.endpoint("")?;
+        if url.origin() != base.origin() || !url.path().starts_with(base.path()) {
+            return Err(KrystalApiError::InvalidParams(format!(
+                "Raw path must stay under the API base URL: {path}"
+            )));
+        }
+        if !params.is_empty() {
+            url.query_pairs_mut().extend_pairs(params);
+        }
+
+        let body = self.get_unchecked(url, options).await?;
+        Ok(serde_json::from_slice(&body)?)
     }
 }
 
@@ -487,22 +1237,42 @@ impl KrystalApiClient {
 impl KrystalApiClient {
     /// Get top pools by TVL for a specific chain
     pub async fn get_top_pools_by_tvl(&self, chain_id: u32, limit: u32) -> Result<Vec<Pool>> {
+        self.get_top_pools_by_tvl_with_options(chain_id, limit, &RequestOptions::default()).await
+    }
+
+    /// [`get_top_pools_by_tvl`](Self::get_top_pools_by_tvl) with [`RequestOptions`]
+    pub async fn get_top_pools_by_tvl_with_options(
+        &self,
+        chain_id: u32,
+        limit: u32,
+        options: &RequestOptions,
+    ) -> Result<Vec<Pool>> {
         let query = PoolsQuery::new()
             .chain_id(chain_id)
             .sort_by(PoolSortBy::Tvl)
             .limit(limit);
 
-        self.get_pools(query).await
+        self.get_pools_with_options(query, options).await
     }
 
     /// Get top pools by 24h volume for a specific chain
     pub async fn get_top_pools_by_volume(&self, chain_id: u32, limit: u32) -> Result<Vec<Pool>> {
+        self.get_top_pools_by_volume_with_options(chain_id, limit, &RequestOptions::default()).await
+    }
+
+    /// [`get_top_pools_by_volume`](Self::get_top_pools_by_volume) with [`RequestOptions`]
+    pub async fn get_top_pools_by_volume_with_options(
+        &self,
+        chain_id: u32,
+        limit: u32,
+        options: &RequestOptions,
+    ) -> Result<Vec<Pool>> {
         let query = PoolsQuery::new()
             .chain_id(chain_id)
             .sort_by(PoolSortBy::Volume24h)
             .limit(limit);
 
-        self.get_pools(query).await
+        self.get_pools_with_options(query, options).await
     }
 
     /// Get pools for a specific token
@@ -510,6 +1280,16 @@ impl KrystalApiClient {
         &self,
         token: &str,
         chain_id: Option<u32>,
+    ) -> Result<Vec<Pool>> {
+        self.get_pools_for_token_with_options(token, chain_id, &RequestOptions::default()).await
+    }
+
+    /// [`get_pools_for_token`](Self::get_pools_for_token) with [`RequestOptions`]
+    pub async fn get_pools_for_token_with_options(
+        &self,
+        token: &str,
+        chain_id: Option<u32>,
+        options: &RequestOptions,
     ) -> Result<Vec<Pool>> {
         let mut query = PoolsQuery::new().token(token);
 
@@ -517,7 +1297,7 @@ impl KrystalApiClient {
             query = query.chain_id(cid);
         }
 
-        self.get_pools(query).await
+        self.get_pools_with_options(query, options).await
     }
 
     /// Get pools for a specific protocol
@@ -526,6 +1306,23 @@ impl KrystalApiClient {
         protocol: &str,
         chain_id: Option<u32>,
         limit: Option<u32>,
+    ) -> Result<Vec<Pool>> {
+        self.get_pools_for_protocol_with_options(
+            protocol,
+            chain_id,
+            limit,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// [`get_pools_for_protocol`](Self::get_pools_for_protocol) with [`RequestOptions`]
+    pub async fn get_pools_for_protocol_with_options(
+        &self,
+        protocol: &str,
+        chain_id: Option<u32>,
+        limit: Option<u32>,
+        options: &RequestOptions,
     ) -> Result<Vec<Pool>> {
         let mut query = PoolsQuery::new().protocol(protocol);
 
@@ -536,7 +1333,7 @@ impl KrystalApiClient {
             query = query.limit(lmt);
         }
 
-        self.get_pools(query).await
+        self.get_pools_with_options(query, options).await
     }
 
     /// Get open positions for a wallet
@@ -544,6 +1341,16 @@ impl KrystalApiClient {
         &self,
         wallet: &str,
         chain_id: Option<u32>,
+    ) -> Result<Vec<Position>> {
+        self.get_open_positions_with_options(wallet, chain_id, &RequestOptions::default()).await
+    }
+
+    /// [`get_open_positions`](Self::get_open_positions) with [`RequestOptions`]
+    pub async fn get_open_positions_with_options(
+        &self,
+        wallet: &str,
+        chain_id: Option<u32>,
+        options: &RequestOptions,
     ) -> Result<Vec<Position>> {
         let mut query = PositionsQuery::new(wallet).status(PositionStatus::Open);
 
@@ -551,7 +1358,7 @@ impl KrystalApiClient {
             query = query.chain_id(cid);
         }
 
-        self.get_positions(query).await
+        self.get_positions_with_options(query, options).await
     }
 
     /// Get closed positions for a wallet
@@ -559,6 +1366,16 @@ impl KrystalApiClient {
         &self,
         wallet: &str,
         chain_id: Option<u32>,
+    ) -> Result<Vec<Position>> {
+        self.get_closed_positions_with_options(wallet, chain_id, &RequestOptions::default()).await
+    }
+
+    /// [`get_closed_positions`](Self::get_closed_positions) with [`RequestOptions`]
+    pub async fn get_closed_positions_with_options(
+        &self,
+        wallet: &str,
+        chain_id: Option<u32>,
+        options: &RequestOptions,
     ) -> Result<Vec<Position>> {
         let mut query = PositionsQuery::new(wallet).status(PositionStatus::Closed);
 
@@ -566,7 +1383,7 @@ impl KrystalApiClient {
             query = query.chain_id(cid);
         }
 
-        self.get_positions(query).await
+        self.get_positions_with_options(query, options).await
     }
 
     /// Get all positions for a wallet (open and closed)
@@ -574,6 +1391,16 @@ impl KrystalApiClient {
         &self,
         wallet: &str,
         chain_id: Option<u32>,
+    ) -> Result<Vec<Position>> {
+        self.get_all_positions_with_options(wallet, chain_id, &RequestOptions::default()).await
+    }
+
+    /// [`get_all_positions`](Self::get_all_positions) with [`RequestOptions`]
+    pub async fn get_all_positions_with_options(
+        &self,
+        wallet: &str,
+        chain_id: Option<u32>,
+        options: &RequestOptions,
     ) -> Result<Vec<Position>> {
         let mut query = PositionsQuery::new(wallet).status(PositionStatus::All);
 
@@ -581,15 +1408,26 @@ impl KrystalApiClient {
             query = query.chain_id(cid);
         }
 
-        self.get_positions(query).await
+        self.get_positions_with_options(query, options).await
     }
 
     /// Get pools with full pagination metadata
-    pub async fn get_pools_paginated(
+    pub async fn get_pools_paginated(&self, query: PoolsQuery) -> Result<PaginatedResponse<Pool>> {
+        self.get_pools_paginated_with_options(query, &RequestOptions::default()).await
+    }
+
+    /// [`get_pools_paginated`](Self::get_pools_paginated) with [`RequestOptions`]
+    pub async fn get_pools_paginated_with_options(
         &self,
         query: PoolsQuery,
+        options: &RequestOptions,
     ) -> Result<PaginatedResponse<Pool>> {
         query.validate().map_err(KrystalApiError::InvalidParams)?;
+        if query.is_multi_chain() {
+            return Err(KrystalApiError::InvalidParams(
+                "Pagination metadata is not available for multi-chain queries".to_string(),
+            ));
+        }
 
         let limit = query.limit;
         let offset = query.offset;
@@ -597,24 +1435,14 @@ impl KrystalApiClient {
         let mut url = self.endpoint("v1/pools")?;
         self.build_pools_query_params(&mut url, &query);
 
-        let json = self.get_with_retry(url).await?;
-
-        let pools_data = json
-            .get("pools")
-            .and_then(|p| p.as_array())
-            .or_else(|| json.as_array())
-            .map(Vec::as_slice)
-            .unwrap_or(&[]);
+        let body = self.get_with_retry(url, options).await?;
+        let envelope: ListEnvelope<Pool> = self.parse_list(&body, "pools", "pools")?;
 
-        let pools: Vec<Pool> = Self::parse_items(pools_data, "pools")?;
-
-        let total = json
-            .get("total")
-            .and_then(|v| v.as_u64());
-        let has_more = json
-            .get("hasMore")
-            .and_then(|v| v.as_bool())
-            .or_else(|| total.map(|t| pools_data.len() as u64 + (offset.unwrap_or(0) as u64) < t));
+        let total = envelope.total;
+        let pools = envelope.items.unwrap_or_default();
+        let has_more = envelope
+            .has_more
+            .or_else(|| total.map(|t| pools.len() as u64 + (offset.unwrap_or(0) as u64) < t));
 
         Ok(PaginatedResponse {
             data: pools,
@@ -625,19 +1453,369 @@ impl KrystalApiClient {
         })
     }
 
+    /// Stream every pool matching a query, fetching pages lazily.
+    ///
+    /// Pages of `query.limit` pools (100 when unset) are requested starting at
+    /// `query.offset` until the API reports no further results or `max_items` pools have
+    /// been yielded. A failed page is yielded as an error and ends the stream.
+    pub fn pools_stream(
+        &self,
+        query: PoolsQuery,
+        max_items: Option<usize>,
+    ) -> impl Stream<Item = Result<Pool>> + '_ {
+        self.pools_stream_with_options(query, max_items, RequestOptions::default())
+    }
+
+    /// [`pools_stream`](Self::pools_stream) with [`RequestOptions`] applied to every page
+    pub fn pools_stream_with_options(
+        &self,
+        query: PoolsQuery,
+        max_items: Option<usize>,
+        options: RequestOptions,
+    ) -> impl Stream<Item = Result<Pool>> + '_ {
+        let page_size = query.limit.unwrap_or(DEFAULT_STREAM_PAGE_SIZE);
+        let paginator = PaginationIterator::starting_at(page_size, query.offset.unwrap_or(0));
+        let state = PoolsStreamState {
+            query,
+            paginator,
+            buffered: VecDeque::new(),
+            remaining: max_items,
+            options,
+        };
+
+        stream::unfold(state, move |mut state| async move {
+            loop {
+                if state.remaining == Some(0) {
+                    return None;
+                }
+                if let Some(pool) = state.buffered.pop_front() {
+                    state.remaining = state.remaining.map(|n| n - 1);
+                    return Some((Ok(pool), state));
+                }
+                if !state.paginator.has_next_page() {
+                    return None;
+                }
+
+                let page_query = state
+                    .query
+                    .clone()
+                    .limit(state.paginator.page_size())
+                    .offset(state.paginator.next_offset());
+                let page = self.get_pools_paginated_with_options(page_query, &state.options);
+                match page.await {
+                    Ok(page) => {
+                        state.paginator.update_from_response(&page);
+                        state.buffered.extend(page.data);
+                    }
+                    Err(e) => {
+                        state.remaining = Some(0);
+                        return Some((Err(e), state));
+                    }
+                }
+            }
+        })
+    }
+
+    /// Download every transaction of a pool between two Unix timestamps.
+    ///
+    /// The range is split into time windows fetched one after another. A window whose
+    /// first page comes back full is halved and refetched, and a window whose page is less
+    /// than half full doubles the next one. Windows still full at the minimum size are
+    /// paged with `offset`. Transactions repeated across window boundaries are dropped by
+    /// hash and log index, and the result is ordered oldest first.
+    pub async fn get_pool_transactions_range(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<Transaction>> {
+        self.get_pool_transactions_range_with_options(
+            chain_id,
+            pool_address,
+            factory_address,
+            start,
+            end,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// [`get_pool_transactions_range`](Self::get_pool_transactions_range) with [`RequestOptions`]
+    pub async fn get_pool_transactions_range_with_options(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        start: u64,
+        end: u64,
+        options: &RequestOptions,
+    ) -> Result<Vec<Transaction>> {
+        self.fetch_transactions_range(
+            chain_id,
+            pool_address,
+            factory_address,
+            (start, end),
+            MAX_TRANSACTIONS_PAGE_SIZE,
+            options,
+        )
+        .await
+    }
+
+    async fn fetch_transactions_range(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        factory_address: Option<&str>,
+        (start, end): (u64, u64),
+        page_size: u32,
+        options: &RequestOptions,
+    ) -> Result<Vec<Transaction>> {
+        if start >= end {
+            return Err(KrystalApiError::InvalidParams(
+                "Start time must be before end time".to_string(),
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        let mut transactions = Vec::new();
+        let mut window = INITIAL_HISTORY_WINDOW.min(end - start);
+        let mut cursor = start;
+
+        while cursor < end {
+            let window_end = cursor.saturating_add(window).min(end);
+            let page_query = TransactionQuery::new()
+                .time_range(cursor, window_end)
+                .limit(page_size);
+            let mut page = self
+                .get_pool_transactions_with_options(
+                    chain_id,
+                    pool_address,
+                    factory_address,
+                    Some(page_query.clone()),
+                    options,
+                )
+                .await?;
+
+            let full = page.len() >= page_size as usize;
+            let sparse = page.len() < page_size as usize / 2;
+            if full && window > MIN_HISTORY_WINDOW {
+                window = (window / 2).max(MIN_HISTORY_WINDOW);
+                debug!("Transaction window full, shrinking to {}s", window);
+                continue;
+            }
+
+            let mut offset = 0;
+            loop {
+                let received = page.len();
+                // One transaction can emit several events; the log index tells them apart
+                transactions.extend(
+                    page.into_iter()
+                        .filter(|tx| seen.insert((tx.hash.clone(), tx.log_index))),
+                );
+                if received < page_size as usize {
+                    break;
+                }
+                offset += page_size;
+                page = self
+                    .get_pool_transactions_with_options(
+                        chain_id,
+                        pool_address,
+                        factory_address,
+                        Some(page_query.clone().offset(offset)),
+                        options,
+                    )
+                    .await?;
+            }
+
+            cursor = window_end;
+            if sparse {
+                window = window.saturating_mul(2).min(MAX_HISTORY_WINDOW);
+            }
+        }
+
+        transactions.sort_by_key(|tx| tx.timestamp);
+        Ok(transactions)
+    }
+
+    /// Fetch details for many pools, at most `concurrency` requests at a time.
+    ///
+    /// Every request still passes through the client's rate limiter, so the cap bounds
+    /// in-flight requests without exceeding `max_requests_per_second`. Results are
+    /// returned in input order, one per pool, so a bad address only fails its own entry.
+    pub async fn get_pool_details(
+        &self,
+        pools: &[(u32, &str)],
+        with_incentives: bool,
+        concurrency: usize,
+    ) -> Vec<Result<Pool>> {
+        self.get_pool_details_with_options(
+            pools,
+            with_incentives,
+            concurrency,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// [`get_pool_details`](Self::get_pool_details) with [`RequestOptions`]
+    pub async fn get_pool_details_with_options(
+        &self,
+        pools: &[(u32, &str)],
+        with_incentives: bool,
+        concurrency: usize,
+        options: &RequestOptions,
+    ) -> Vec<Result<Pool>> {
+        stream::iter(pools)
+            .map(|&(chain_id, address)| {
+                self.get_pool_detail_with_options(chain_id, address, None, with_incentives, options)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Fetch positions for many wallets, at most `concurrency` requests at a time.
+    ///
+    /// Results are returned in input order, one per wallet; see
+    /// [`get_pool_details`](Self::get_pool_details) for how the cap interacts with rate
+    /// limiting.
+    pub async fn get_positions_many(
+        &self,
+        wallets: &[&str],
+        chain_id: Option<u32>,
+        concurrency: usize,
+    ) -> Vec<Result<Vec<Position>>> {
+        self.get_positions_many_with_options(
+            wallets,
+            chain_id,
+            concurrency,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// [`get_positions_many`](Self::get_positions_many) with [`RequestOptions`]
+    pub async fn get_positions_many_with_options(
+        &self,
+        wallets: &[&str],
+        chain_id: Option<u32>,
+        concurrency: usize,
+        options: &RequestOptions,
+    ) -> Vec<Result<Vec<Position>>> {
+        stream::iter(wallets)
+            .map(|&wallet| {
+                let mut query = PositionsQuery::new(wallet);
+                if let Some(cid) = chain_id {
+                    query = query.chain_id(cid);
+                }
+                self.get_positions_with_options(query, options)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
     /// Get recent transactions for a pool
     pub async fn get_recent_pool_transactions(
         &self,
         chain_id: u32,
         pool_address: &str,
         limit: u32,
+    ) -> Result<Vec<Transaction>> {
+        self.get_recent_pool_transactions_with_options(
+            chain_id,
+            pool_address,
+            limit,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// [`get_recent_pool_transactions`](Self::get_recent_pool_transactions) with [`RequestOptions`]
+    pub async fn get_recent_pool_transactions_with_options(
+        &self,
+        chain_id: u32,
+        pool_address: &str,
+        limit: u32,
+        options: &RequestOptions,
     ) -> Result<Vec<Transaction>> {
         let query = TransactionQuery::new().limit(limit);
-        self.get_pool_transactions(chain_id, pool_address, None, Some(query))
+        self.get_pool_transactions_with_options(chain_id, pool_address, None, Some(query), options)
             .await
     }
 }
 
+/// Largest `limit` accepted by `PoolsQuery::validate`
+const MAX_POOLS_PAGE_SIZE: u32 = 1000;
+
+/// Largest `limit` the pool transactions endpoint accepts
+const MAX_TRANSACTIONS_PAGE_SIZE: u32 = 5000;
+
+/// Window sizes (seconds) used by [`KrystalApiClient::get_pool_transactions_range`]
+const INITIAL_HISTORY_WINDOW: u64 = 24 * 60 * 60;
+const MIN_HISTORY_WINDOW: u64 = 60;
+const MAX_HISTORY_WINDOW: u64 = 7 * 24 * 60 * 60;
+
+/// Default page size used by [`KrystalApiClient::pools_stream`]
+const DEFAULT_STREAM_PAGE_SIZE: u32 = 100;
+
+/// Progress of a [`KrystalApiClient::pools_stream`]
+struct PoolsStreamState {
+    query: PoolsQuery,
+    paginator: PaginationIterator<Pool>,
+    buffered: VecDeque<Pool>,
+    remaining: Option<usize>,
+    options: RequestOptions,
+}
+
+/// Map a deserialization failure to the error returned for malformed responses
+fn parse_error(context: &str, error: serde_json::Error) -> KrystalApiError {
+    KrystalApiError::InvalidParams(format!("Failed to parse {context} response: {error}"))
+}
+
+/// Registration of the leading request for a coalescing key.
+///
+/// Removes the key when completed or dropped, so a cancelled leader never leaves
+/// followers waiting forever.
+struct InflightLeader<'a> {
+    inflight: &'a InflightMap,
+    key: String,
+    sender: Option<watch::Sender<Option<SharedResult>>>,
+}
+
+impl InflightLeader<'_> {
+    /// Publish the result to any callers waiting on this key
+    fn complete(mut self, result: &Result<Bytes>) {
+        self.unregister();
+        if let Some(sender) = self.sender.take()
+            && sender.receiver_count() > 0
+        {
+            let shared = match result {
+                Ok(body) => Ok(body.clone()),
+                Err(e) => Err(e.duplicate()),
+            };
+            let _ = sender.send(Some(Arc::new(shared)));
+        }
+    }
+
+    fn unregister(&self) {
+        self.inflight
+            .lock()
+            .expect("inflight map poisoned")
+            .remove(&self.key);
+    }
+}
+
+impl Drop for InflightLeader<'_> {
+    fn drop(&mut self) {
+        if self.sender.is_some() {
+            self.unregister();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -676,6 +1854,7 @@ mod tests {
             user_agent: "test-client/1.0".to_string(),
             retry: RetryConfig::default(),
             max_requests_per_second: 5,
+            ..ClientConfig::default()
         };
 
         let client = KrystalApiClient::with_config("test-key".to_string(), config);
@@ -753,4 +1932,136 @@ mod tests {
         assert!(!debug_str.contains("super-secret-key"));
         assert!(debug_str.contains("[redacted]"));
     }
+
+    #[test]
+    fn test_debug_redacts_fallback_keys() {
+        let config = ClientConfig {
+            api_keys: vec![ApiKey::new("fallback-secret-1"), ApiKey::new("fallback-secret-2")],
+            ..ClientConfig::default()
+        };
+        let client = KrystalApiClient::with_config("primary-secret".to_string(), config).unwrap();
+        assert_eq!(client.key_count(), 3);
+
+        let debug_str = format!("{:?}", client);
+        assert!(!debug_str.contains("secret"));
+        assert!(debug_str.contains(&ApiKey::new("fallback-secret-2").fingerprint()));
+    }
+
+    fn transactions_body(txs: &[(&str, u64)]) -> serde_json::Value {
+        let txs: Vec<_> = txs
+            .iter()
+            .map(|(hash, timestamp)| {
+                serde_json::json!({
+                    "hash": hash, "timestamp": timestamp, "type": "swap",
+                    "amount0": 1.0, "amount1": -1.0
+                })
+            })
+            .collect();
+        serde_json::json!({ "transactions": txs })
+    }
+
+    fn range_client(transport: Arc<crate::transport::MockTransport>) -> KrystalApiClient {
+        let config = ClientConfig {
+            max_requests_per_second: 1000,
+            ..ClientConfig::default()
+        };
+        KrystalApiClient::with_transport("test".to_string(), config, transport).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_transactions_range_shrinks_full_windows_and_dedupes() {
+        let path = "/v1/pools/1/0xpool/transactions";
+        let transport = Arc::new(
+            crate::transport::MockTransport::new()
+                .with_json(path, transactions_body(&[("a", 10), ("b", 90), ("c", 150)]))
+                .with_json(path, transactions_body(&[("b", 90), ("a", 10)]))
+                .with_json(path, transactions_body(&[("b", 100), ("d", 150)])),
+        );
+        let client = range_client(transport.clone());
+
+        let txs = client
+            .fetch_transactions_range(1, "0xpool", None, (0, 200), 3, &RequestOptions::default())
+            .await
+            .unwrap();
+        let hashes: Vec<_> = txs.iter().map(|tx| tx.hash.as_str()).collect();
+        assert_eq!(hashes, ["a", "b", "d"]);
+
+        let queries: Vec<_> = transport
+            .requests()
+            .iter()
+            .map(|r| r.url.query().unwrap_or("").to_string())
+            .collect();
+        assert_eq!(queries.len(), 3);
+        assert!(queries[0].contains("startTime=0&endTime=200"));
+        assert!(queries[1].contains("startTime=0&endTime=100"));
+        assert!(queries[2].contains("startTime=100&endTime=200"));
+    }
+
+    #[tokio::test]
+    async fn test_transactions_range_pages_full_minimum_window() {
+        let path = "/v1/pools/1/0xpool/transactions";
+        let transport = Arc::new(
+            crate::transport::MockTransport::new()
+                .with_json(path, transactions_body(&[("b", 20), ("a", 10)]))
+                .with_json(path, transactions_body(&[("c", 30)])),
+        );
+        let client = range_client(transport.clone());
+
+        let txs = client
+            .fetch_transactions_range(
+                1,
+                "0xpool",
+                None,
+                (0, MIN_HISTORY_WINDOW),
+                2,
+                &RequestOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(txs.len(), 3);
+        assert_eq!(txs[0].hash, "a");
+
+        let second = transport.requests()[1].url.query().unwrap_or("").to_string();
+        assert!(second.contains("offset=2"));
+    }
+
+    #[tokio::test]
+    async fn test_transactions_range_keeps_events_sharing_a_hash() {
+        let path = "/v1/pools/1/0xpool/transactions";
+        let event = |log_index: u32| {
+            serde_json::json!({"hash": "a", "timestamp": 10, "type": "swap", "amount0": 1,
+                "amount1": 2, "logIndex": log_index})
+        };
+        let transport = Arc::new(crate::transport::MockTransport::new().with_json(
+            path,
+            serde_json::json!({"transactions": [event(1), event(4), event(1)]}),
+        ));
+        let client = range_client(transport);
+
+        let txs = client
+            .fetch_transactions_range(1, "0xpool", None, (0, 200), 10, &RequestOptions::default())
+            .await
+            .unwrap();
+        let log_indexes: Vec<_> = txs.iter().map(|tx| tx.log_index).collect();
+        assert_eq!(log_indexes, [Some(1), Some(4)]);
+    }
+
+    #[tokio::test]
+    async fn test_transactions_range_rejects_empty_range() {
+        let client = range_client(Arc::new(crate::transport::MockTransport::new()));
+        let err = client
+            .get_pool_transactions_range(1, "0xpool", None, 100, 100)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, KrystalApiError::InvalidParams(_)));
+    }
+
+    #[test]
+    fn test_client_accepts_fallback_keys_without_primary() {
+        let config = ClientConfig {
+            api_keys: vec![ApiKey::new("fallback")],
+            ..ClientConfig::default()
+        };
+        assert!(KrystalApiClient::with_config(String::new(), config).is_ok());
+    }
 }
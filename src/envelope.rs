@@ -0,0 +1,156 @@
+// file: src/envelope.rs
+// description: Response envelopes deserialized straight from body bytes, covering the bare
+//             array and `{"pools": [...]}`-style object shapes returned by list endpoints
+// docs_reference: https://serde.rs/impl-deserialize.html
+
+use serde::de::{
+    Deserialize, DeserializeOwned, DeserializeSeed, Deserializer, IgnoredAny, MapAccess,
+    SeqAccess, Visitor,
+};
+use serde_json::Value;
+use serde_json::value::RawValue;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A list response: either a bare JSON array or an object wrapping it under an
+/// endpoint-specific key such as `pools`, optionally with `total` and `hasMore` fields.
+///
+/// Items are deserialized directly from the body bytes; other object keys are skipped
+/// without being buffered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListEnvelope<T> {
+    /// Items, or `None` when an object response had no list key (or it was null)
+    pub items: Option<Vec<T>>,
+    /// Total number of matching items, when reported
+    pub total: Option<u64>,
+    /// Whether more items are available, when reported
+    pub has_more: Option<bool>,
+}
+
+impl<T: DeserializeOwned> ListEnvelope<T> {
+    /// Parse a list response whose object form wraps the items under `key`
+    pub fn from_slice(body: &[u8], key: &str) -> serde_json::Result<Self> {
+        let mut deserializer = serde_json::Deserializer::from_slice(body);
+        let envelope = ListSeed {
+            key,
+            marker: PhantomData,
+        }
+        .deserialize(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(envelope)
+    }
+}
+
+impl<T> ListEnvelope<T> {
+    /// Items, treating a missing list as empty
+    pub fn into_items(self) -> Vec<T> {
+        self.items.unwrap_or_default()
+    }
+}
+
+struct ListSeed<'k, T> {
+    key: &'k str,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for ListSeed<'_, T> {
+    type Value = ListEnvelope<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ListSeed<'_, T> {
+    type Value = ListEnvelope<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an array or an object with a `{}` array", self.key)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(ListEnvelope {
+            items: Some(items),
+            total: None,
+            has_more: None,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut items = None;
+        let mut total = None;
+        let mut has_more = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                k if k == self.key => items = map.next_value::<Option<Vec<T>>>()?,
+                // Pagination fields are small scalars; wrong types are ignored
+                "total" => total = map.next_value::<Value>()?.as_u64(),
+                "hasMore" => has_more = map.next_value::<Value>()?.as_bool(),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(ListEnvelope {
+            items,
+            total,
+            has_more,
+        })
+    }
+}
+
+/// A chain stats response, which may wrap the stats under `chain`; the wrapped value is
+/// borrowed from the body rather than parsed
+#[derive(Debug, serde::Deserialize)]
+pub(crate) struct ChainEnvelope<'a> {
+    #[serde(borrow)]
+    pub chain: Option<&'a RawValue>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    struct Item {
+        id: u32,
+    }
+
+    fn parse(body: &str, key: &str) -> serde_json::Result<ListEnvelope<Item>> {
+        ListEnvelope::from_slice(body.as_bytes(), key)
+    }
+
+    #[test]
+    fn test_list_envelope_shapes() {
+        let bare = parse(r#"[{"id": 1}, {"id": 2}]"#, "pools").unwrap();
+        assert_eq!(bare.items, Some(vec![Item { id: 1 }, Item { id: 2 }]));
+
+        let body = r#"{"chains": [{"id": "x"}], "pools": [{"id": 3}], "total": 7}"#;
+        let wrapped = parse(body, "pools").unwrap();
+        assert_eq!(wrapped.items, Some(vec![Item { id: 3 }]));
+        assert_eq!(wrapped.total, Some(7));
+        assert_eq!(wrapped.has_more, None);
+
+        let paged = parse(r#"{"positions": [], "hasMore": true, "total": "n/a"}"#, "positions");
+        let paged = paged.unwrap();
+        assert_eq!(paged.items, Some(vec![]));
+        assert_eq!((paged.total, paged.has_more), (None, Some(true)));
+
+        let missing = parse(r#"{"message": "ok"}"#, "transactions").unwrap();
+        assert_eq!(missing.items, None);
+        assert!(missing.into_items().is_empty());
+    }
+
+    #[test]
+    fn test_list_envelope_rejects_bad_bodies() {
+        assert!(parse(r#"{"chains": [{"id": "x"}]}"#, "chains").is_err());
+        assert!(parse("42", "chains").is_err());
+        assert!(parse("[] trailing", "chains").is_err());
+    }
+}
@@ -4,6 +4,7 @@
 // docs_reference: https://docs.rs/thiserror/latest/thiserror/
 
 use reqwest::Error as ReqwestError;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Custom error types for better error handling and debugging.
@@ -25,6 +26,13 @@ pub enum KrystalApiError {
     #[error("Payment required: No credit left")]
     PaymentRequired,
 
+    /// API throttled the request (HTTP 429)
+    #[error("Rate limited by API")]
+    RateLimited {
+        /// Wait requested by the server's `Retry-After` header, if any
+        retry_after: Option<Duration>,
+    },
+
     /// Invalid parameters provided
     #[error("Invalid parameters: {0}")]
     InvalidParams(String),
@@ -40,6 +48,51 @@ pub enum KrystalApiError {
     /// Environment variable error
     #[error("Environment variable error: {0}")]
     EnvError(#[from] std::env::VarError),
+
+    /// Local file I/O error (cassettes, caches)
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    /// Replay mode found no recorded response for a request
+    #[error("No recorded response for {0}")]
+    CassetteMiss(String),
+
+    /// An HTTP attempt exceeded the per-call timeout
+    #[error("Request timed out after {}ms", .0.as_millis())]
+    Timeout(Duration),
+
+    /// The call's deadline passed before it completed
+    #[error("Deadline exceeded")]
+    DeadlineExceeded,
+
+    /// The call was cancelled through its cancellation token
+    #[error("Request cancelled")]
+    Cancelled,
+
+    /// Strict parse mode found fields the models do not declare
+    #[error("Unexpected fields in {context} response: {}", fields.join(", "))]
+    SchemaDrift {
+        /// Response being parsed, e.g. "pools"
+        context: String,
+        /// Unknown fields as `Model.key`
+        fields: Vec<String>,
+    },
+
+    /// Spec check mode found a request that does not match `api.json`; it was not sent
+    #[error("Request to {path} does not match the API spec: {}", violations.join("; "))]
+    SpecViolation {
+        /// API path of the request
+        path: String,
+        /// Mismatches found
+        violations: Vec<String>,
+    },
+
+    /// Circuit breaker is open after sustained upstream failures; the request was not sent
+    #[error("Circuit breaker open, retry in {}s", retry_in.as_secs())]
+    CircuitOpen {
+        /// Time until the breaker lets a probe request through
+        retry_in: Duration,
+    },
 }
 
 /// Result type alias for convenience
@@ -51,6 +104,8 @@ impl KrystalApiError {
         matches!(
             self,
             Self::RequestError(_)
+                | Self::Timeout(_)
+                | Self::RateLimited { .. }
                 | Self::ApiError {
                     status: 500..=599,
                     ..
@@ -58,6 +113,14 @@ impl KrystalApiError {
         )
     }
 
+    /// Server-requested wait before retrying, if any
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+
     /// Check if error is related to authentication
     pub fn is_auth_error(&self) -> bool {
         matches!(self, Self::AuthError)
@@ -68,6 +131,48 @@ impl KrystalApiError {
         matches!(self, Self::PaymentRequired)
     }
 
+    /// Copy of this error for callers sharing a coalesced request.
+    ///
+    /// Transport and JSON errors cannot be cloned, so they are carried over by message;
+    /// a transport error becomes an `ApiError` with its HTTP status (0 when unknown).
+    pub(crate) fn duplicate(&self) -> Self {
+        match self {
+            Self::RequestError(e) => Self::ApiError {
+                status: e.status().map_or(0, |s| s.as_u16()),
+                message: e.to_string(),
+            },
+            Self::ApiError { status, message } => Self::ApiError {
+                status: *status,
+                message: message.clone(),
+            },
+            Self::AuthError => Self::AuthError,
+            Self::PaymentRequired => Self::PaymentRequired,
+            Self::RateLimited { retry_after } => Self::RateLimited {
+                retry_after: *retry_after,
+            },
+            Self::InvalidParams(msg) => Self::InvalidParams(msg.clone()),
+            Self::UrlError(e) => Self::UrlError(*e),
+            Self::JsonError(e) => Self::JsonError(serde::de::Error::custom(e.to_string())),
+            Self::EnvError(e) => Self::EnvError(e.clone()),
+            Self::IoError(e) => Self::IoError(std::io::Error::new(e.kind(), e.to_string())),
+            Self::CassetteMiss(url) => Self::CassetteMiss(url.clone()),
+            Self::Timeout(limit) => Self::Timeout(*limit),
+            Self::DeadlineExceeded => Self::DeadlineExceeded,
+            Self::Cancelled => Self::Cancelled,
+            Self::SchemaDrift { context, fields } => Self::SchemaDrift {
+                context: context.clone(),
+                fields: fields.clone(),
+            },
+            Self::SpecViolation { path, violations } => Self::SpecViolation {
+                path: path.clone(),
+                violations: violations.clone(),
+            },
+            Self::CircuitOpen { retry_in } => Self::CircuitOpen {
+                retry_in: *retry_in,
+            },
+        }
+    }
+
     /// Get user-friendly error message with suggested actions
     pub fn user_message(&self) -> String {
         match self {
@@ -77,6 +182,14 @@ impl KrystalApiError {
             Self::PaymentRequired => {
                 "Your account has no remaining credits. Please top up your balance to continue.".to_string()
             }
+            Self::RateLimited {
+                retry_after: Some(wait),
+            } => {
+                format!("The API is rate limiting requests. Please wait {}s and try again.", wait.as_secs().max(1))
+            }
+            Self::RateLimited { retry_after: None } => {
+                "The API is rate limiting requests. Please wait a moment and try again.".to_string()
+            }
             Self::RequestError(e) if e.is_timeout() => {
                 "Request timed out. Please try again or check your internet connection.".to_string()
             }
@@ -86,6 +199,12 @@ impl KrystalApiError {
             Self::InvalidParams(msg) => {
                 format!("Invalid request parameters: {}", msg)
             }
+            Self::CassetteMiss(url) => {
+                format!("No recorded response for {}. Run once with --record to capture it.", url)
+            }
+            Self::CircuitOpen { retry_in } => {
+                format!("The API is failing repeatedly; requests are paused for {}s.", retry_in.as_secs().max(1))
+            }
             _ => self.to_string(),
         }
     }
@@ -18,20 +18,49 @@
 //! }
 //! ```
 
+pub mod address;
+pub mod amount;
+pub mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cache;
+pub mod cassette;
 pub mod client;
+pub mod envelope;
 pub mod error;
+pub mod middleware;
 pub mod models;
+pub mod position_id;
 pub mod query;
+pub mod schema;
+pub mod spec;
+pub mod transport;
+pub mod usage;
 pub mod utils;
 
 pub mod cli;
 
-pub use client::{ClientConfig, KrystalApiClient};
+pub use address::Address;
+pub use amount::TokenAmount;
+pub use auth::ApiKey;
+pub use cache::{CacheConfig, CacheTtls, DiskCache, EndpointFamily, MemoryCacheConfig};
+pub use cassette::CassetteMode;
+pub use envelope::ListEnvelope;
+pub use client::{ClientConfig, KrystalApiClient, RequestOptions};
 pub use error::{KrystalApiError, Result};
+pub use middleware::{Middleware, MiddlewareStack, ResponseInfo};
 pub use models::{
     ChainInfo, ChainStats, PaginatedResponse, Pool, PoolHistoricalData, PoolHistoricalPoint,
-    PoolSortBy, Position, PositionStatus, ProtocolSummary, Transaction,
+    PoolSortBy, Position, PositionRangeStatus, PositionStatus, ProtocolSummary, Transaction,
+    TransactionKind,
 };
+pub use position_id::PositionId;
 pub use query::{PoolsQuery, PositionsQuery, TransactionQuery};
+pub use schema::{ParseMode, SchemaModel, SchemaReport};
+pub use spec::{ApiSpec, SpecCheck, SpecViolation};
+pub use transport::{HttpTransport, MockTransport};
+pub use usage::{UsageLedger, UsageReport};
+pub use utils::circuit_breaker::{CircuitBreakerConfig, CircuitState};
+pub use tokio_util::sync::CancellationToken;
 
 pub use cli::app::run_cli;
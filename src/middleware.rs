@@ -0,0 +1,142 @@
+// file: src/middleware.rs
+// description: Request/response hooks registered on the client configuration, used for custom
+//             headers, audit logging and metrics without changing the request pipeline
+// docs_reference: https://docs.rs/reqwest/latest/reqwest/header/struct.HeaderMap.html
+
+use crate::error::Result;
+use bytes::Bytes;
+use crate::transport::HttpRequest;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// Outcome of a single HTTP attempt, passed to [`Middleware::after_response`]
+#[derive(Debug)]
+pub struct ResponseInfo<'a> {
+    /// URL that was requested (after `before_request` hooks ran)
+    pub url: &'a Url,
+    /// HTTP status, or `None` when the transport failed before a response arrived
+    pub status: Option<u16>,
+    /// Time spent in the transport
+    pub latency: Duration,
+    /// Raw JSON body or the error the client is about to handle
+    pub result: &'a Result<Bytes>,
+}
+
+impl ResponseInfo<'_> {
+    /// Parse a successful body into a JSON value; `None` for errors
+    pub fn json(&self) -> Option<Value> {
+        let body = self.result.as_ref().ok()?;
+        serde_json::from_slice(body).ok()
+    }
+}
+
+/// Hooks called around every HTTP attempt, including retries and key failover.
+///
+/// Responses served from the memory or disk cache do not reach the transport and
+/// therefore skip middleware.
+pub trait Middleware: Send + Sync {
+    /// Called before the request is sent; may rewrite the URL or headers
+    fn before_request(&self, _request: &mut HttpRequest) {}
+
+    /// Called after the response has been checked or mapped to an error
+    fn after_response(&self, _response: &ResponseInfo<'_>) {}
+}
+
+/// Ordered list of middlewares; `before_request` and `after_response` run in insertion order
+#[derive(Clone, Default)]
+pub struct MiddlewareStack {
+    layers: Vec<Arc<dyn Middleware>>,
+}
+
+impl std::fmt::Debug for MiddlewareStack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MiddlewareStack")
+            .field("layers", &self.layers.len())
+            .finish()
+    }
+}
+
+impl MiddlewareStack {
+    /// Create an empty stack
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a middleware to the end of the stack
+    pub fn with<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.push(Arc::new(middleware));
+        self
+    }
+
+    /// Append a shared middleware to the end of the stack
+    pub fn push(&mut self, middleware: Arc<dyn Middleware>) {
+        self.layers.push(middleware);
+    }
+
+    /// Number of registered middlewares
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Whether no middleware is registered
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    pub(crate) fn before_request(&self, request: &mut HttpRequest) {
+        for layer in &self.layers {
+            layer.before_request(request);
+        }
+    }
+
+    pub(crate) fn after_response(&self, response: &ResponseInfo<'_>) {
+        for layer in &self.layers {
+            layer.after_response(response);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct Tag(&'static str, Arc<Mutex<Vec<String>>>);
+
+    impl Middleware for Tag {
+        fn before_request(&self, request: &mut HttpRequest) {
+            let path = format!("{}/{}", request.url.path().trim_end_matches('/'), self.0);
+            request.url.set_path(&path);
+        }
+
+        fn after_response(&self, response: &ResponseInfo<'_>) {
+            self.1.lock().unwrap().push(format!("{}:{:?}", self.0, response.status));
+        }
+    }
+
+    #[test]
+    fn test_stack_runs_in_order() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let stack = MiddlewareStack::new()
+            .with(Tag("a", seen.clone()))
+            .with(Tag("b", seen.clone()));
+        assert_eq!(stack.len(), 2);
+
+        let mut request = HttpRequest::new(Url::parse("https://example.com/v1").unwrap());
+        stack.before_request(&mut request);
+        assert_eq!(request.url.path(), "/v1/a/b");
+
+        let result = Ok(Bytes::from(r#"{"ok":true}"#));
+        let response = ResponseInfo {
+            url: &request.url,
+            status: Some(200),
+            latency: Duration::ZERO,
+            result: &result,
+        };
+        stack.after_response(&response);
+        assert_eq!(response.json(), Some(serde_json::json!({"ok": true})));
+        assert_eq!(*seen.lock().unwrap(), ["a:Some(200)", "b:Some(200)"]);
+    }
+}
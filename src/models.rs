@@ -3,6 +3,10 @@
 //             representations of chains, pools, positions, and transactions with serde support
 // docs_reference: https://docs.rs/serde/latest/serde/
 
+use crate::address::Address;
+use crate::amount::TokenAmount;
+use crate::error::KrystalApiError;
+use crate::position_id::PositionId;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -35,7 +39,7 @@ pub struct ProtocolSummary {
     pub key: String,
     pub name: String,
     #[serde(rename = "factoryAddress")]
-    pub factory_address: Option<String>,
+    pub factory_address: Option<Address>,
     pub logo: Option<String>,
     #[serde(flatten)]
     pub additional_fields: HashMap<String, serde_json::Value>,
@@ -114,7 +118,7 @@ pub struct Pool {
     pub chain: Option<ChainInfo>,
     /// Contract address of the pool
     #[serde(rename = "poolAddress")]
-    pub address: String,
+    pub address: Address,
     /// Pool price (token0 in terms of token1)
     #[serde(rename = "poolPrice")]
     pub pool_price: f64, // Changed from Option<String> to f64
@@ -153,7 +157,7 @@ pub struct ProtocolInfo {
     pub name: String,
     /// Factory contract address
     #[serde(rename = "factoryAddress")]
-    pub factory_address: String,
+    pub factory_address: Address,
     /// Protocol logo URL
     pub logo: Option<String>,
 }
@@ -162,7 +166,7 @@ pub struct ProtocolInfo {
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct TokenInfo {
     /// Token contract address
-    pub address: String,
+    pub address: Address,
     /// Token symbol
     pub symbol: String,
     /// Token name
@@ -213,15 +217,15 @@ pub struct Position {
     pub pool: Option<PoolInfo>,
     /// Owner wallet address
     #[serde(rename = "ownerAddress")]
-    pub owner_address: String,
+    pub owner_address: Address,
     /// NFT token address
     #[serde(rename = "tokenAddress")]
-    pub token_address: String,
+    pub token_address: Address,
     /// NFT token ID
     #[serde(rename = "tokenId")]
     pub token_id: String,
-    /// Position liquidity
-    pub liquidity: String,
+    /// Position liquidity (raw integer, no decimals)
+    pub liquidity: TokenAmount,
     /// Minimum price range
     #[serde(rename = "minPrice")]
     pub min_price: f64,
@@ -231,8 +235,8 @@ pub struct Position {
     /// Current position value in USD
     #[serde(rename = "currentPositionValue")]
     pub current_position_value: f64,
-    /// Status of the position
-    pub status: String,
+    /// Range status of the position
+    pub status: PositionRangeStatus,
     /// Current token amounts
     #[serde(rename = "currentAmounts")]
     pub current_amounts: Option<Vec<TokenWithValue>>,
@@ -259,24 +263,68 @@ pub struct PoolInfo {
     pub id: String,
     /// Pool address
     #[serde(rename = "poolAddress")]
-    pub pool_address: String,
+    pub pool_address: Address,
     /// Protocol information
     pub protocol: Option<ProtocolInfo>,
 }
 
 /// Token with value information
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(try_from = "RawTokenWithValue", into = "RawTokenWithValue")]
 pub struct TokenWithValue {
     /// Token details
     pub token: TokenInfo,
-    /// Token balance
-    pub balance: String,
+    /// Token balance, scaled by `token.decimals`
+    pub balance: TokenAmount,
     /// Token price in USD
     pub price: f64,
     /// Total value in USD
     pub value: f64,
 }
 
+impl TokenWithValue {
+    /// Balance valued at `price`, computed from the exact balance
+    pub fn balance_usd(&self) -> f64 {
+        self.balance.usd_value(self.price)
+    }
+}
+
+/// Wire form of [`TokenWithValue`]: the balance is a raw integer string, and a balance
+/// written in token units is rejected rather than guessed at
+#[derive(Deserialize, Serialize)]
+struct RawTokenWithValue {
+    token: TokenInfo,
+    balance: String,
+    price: f64,
+    value: f64,
+}
+
+impl TryFrom<RawTokenWithValue> for TokenWithValue {
+    type Error = KrystalApiError;
+
+    fn try_from(raw: RawTokenWithValue) -> Result<Self, Self::Error> {
+        let balance = TokenAmount::from_raw(&raw.balance, raw.token.decimals)?;
+        Ok(Self {
+            token: raw.token,
+            balance,
+            price: raw.price,
+            value: raw.value,
+        })
+    }
+}
+
+impl From<TokenWithValue> for RawTokenWithValue {
+    fn from(amount: TokenWithValue) -> Self {
+        let balance = amount.balance.round(amount.token.decimals).raw().to_string();
+        Self {
+            token: amount.token,
+            balance,
+            price: amount.price,
+            value: amount.value,
+        }
+    }
+}
+
 /// Fee information (pending and claimed)
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct FeeInfo {
@@ -325,24 +373,114 @@ pub struct AprBreakdown {
 }
 
 /// Information about a transaction
+///
+/// `amount0`/`amount1` arrive as JSON numbers in token units. When `token0`/`token1` are
+/// returned the amounts are scaled to those tokens' decimals (rounding away float noise),
+/// otherwise their decimals are the fractional digits given. They serialize back as numbers.
+///
+/// The participant, block and token fields are read under one name each and are optional,
+/// since the published spec leaves the response shape open; `schema-check` reports when the
+/// API stops sending them and where they land in `additional_fields` under other names.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(from = "RawTransaction", into = "RawTransaction")]
 pub struct Transaction {
     /// Transaction hash
     pub hash: String,
     /// Unix timestamp of the transaction
     pub timestamp: u64,
-    /// Type of transaction (e.g., "swap", "mint", "burn")
-    #[serde(rename = "type")]
-    pub transaction_type: String,
-    /// Amount of token0 involved
-    pub amount0: f64,
-    /// Amount of token1 involved
-    pub amount1: f64,
+    /// Type of transaction (e.g., swap, mint, burn)
+    pub transaction_type: TransactionKind,
+    /// Amount of token0 involved, in token units, scaled by `token0.decimals` when known
+    pub amount0: TokenAmount,
+    /// Amount of token1 involved, in token units, scaled by `token1.decimals` when known
+    pub amount1: TokenAmount,
+    /// Address that sent the transaction
+    pub sender: Option<Address>,
+    /// Address that received the output
+    pub recipient: Option<Address>,
+    /// Block the transaction was included in
+    pub block_number: Option<u64>,
+    /// Index of the event log within the block
+    pub log_index: Option<u32>,
+    /// Total value moved in USD
+    pub value_usd: Option<f64>,
+    /// Token that `amount0` is denominated in
+    pub token0: Option<TokenInfo>,
+    /// Token that `amount1` is denominated in
+    pub token1: Option<TokenInfo>,
     /// Additional fields that might be present in the API response
-    #[serde(flatten)]
     pub additional_fields: HashMap<String, serde_json::Value>,
 }
 
+/// Wire form of [`Transaction`]: amounts are JSON numbers with no decimals attached
+#[derive(Deserialize, Serialize)]
+struct RawTransaction {
+    hash: String,
+    timestamp: u64,
+    #[serde(rename = "type")]
+    transaction_type: TransactionKind,
+    #[serde(serialize_with = "crate::amount::serialize_as_number")]
+    amount0: TokenAmount,
+    #[serde(serialize_with = "crate::amount::serialize_as_number")]
+    amount1: TokenAmount,
+    sender: Option<Address>,
+    recipient: Option<Address>,
+    #[serde(rename = "blockNumber")]
+    block_number: Option<u64>,
+    #[serde(rename = "logIndex")]
+    log_index: Option<u32>,
+    #[serde(rename = "valueUsd")]
+    value_usd: Option<f64>,
+    token0: Option<TokenInfo>,
+    token1: Option<TokenInfo>,
+    #[serde(flatten)]
+    additional_fields: HashMap<String, serde_json::Value>,
+}
+
+impl From<RawTransaction> for Transaction {
+    fn from(raw: RawTransaction) -> Self {
+        let scale = |amount: TokenAmount, token: &Option<TokenInfo>| match token {
+            Some(token) => amount.round(token.decimals),
+            None => amount,
+        };
+        Self {
+            amount0: scale(raw.amount0, &raw.token0),
+            amount1: scale(raw.amount1, &raw.token1),
+            hash: raw.hash,
+            timestamp: raw.timestamp,
+            transaction_type: raw.transaction_type,
+            sender: raw.sender,
+            recipient: raw.recipient,
+            block_number: raw.block_number,
+            log_index: raw.log_index,
+            value_usd: raw.value_usd,
+            token0: raw.token0,
+            token1: raw.token1,
+            additional_fields: raw.additional_fields,
+        }
+    }
+}
+
+impl From<Transaction> for RawTransaction {
+    fn from(tx: Transaction) -> Self {
+        Self {
+            hash: tx.hash,
+            timestamp: tx.timestamp,
+            transaction_type: tx.transaction_type,
+            amount0: tx.amount0,
+            amount1: tx.amount1,
+            sender: tx.sender,
+            recipient: tx.recipient,
+            block_number: tx.block_number,
+            log_index: tx.log_index,
+            value_usd: tx.value_usd,
+            token0: tx.token0,
+            token1: tx.token1,
+            additional_fields: tx.additional_fields,
+        }
+    }
+}
+
 /// Response wrapper for paginated results
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PaginatedResponse<T> {
@@ -399,6 +537,134 @@ impl PositionStatus {
     }
 }
 
+/// Range status reported for a position
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum PositionRangeStatus {
+    /// Open, with the current price inside the position's range
+    InRange,
+    /// Open, with the current price outside the position's range
+    OutOfRange,
+    /// Position has been closed
+    Closed,
+    /// A status this client does not know, kept verbatim
+    Unknown(String),
+}
+
+impl PositionRangeStatus {
+    /// API spelling of the status
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::InRange => "IN_RANGE",
+            Self::OutOfRange => "OUT_RANGE",
+            Self::Closed => "CLOSED",
+            Self::Unknown(status) => status,
+        }
+    }
+
+    /// Whether the position is still open (in or out of range)
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::InRange | Self::OutOfRange)
+    }
+}
+
+impl From<String> for PositionRangeStatus {
+    fn from(status: String) -> Self {
+        match status.to_ascii_uppercase().as_str() {
+            "IN_RANGE" => Self::InRange,
+            "OUT_RANGE" | "OUT_OF_RANGE" => Self::OutOfRange,
+            "CLOSED" => Self::Closed,
+            _ => Self::Unknown(status),
+        }
+    }
+}
+
+impl From<PositionRangeStatus> for String {
+    fn from(status: PositionRangeStatus) -> Self {
+        match status {
+            PositionRangeStatus::Unknown(status) => status,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for PositionRangeStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Kind of a pool or position transaction
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum TransactionKind {
+    /// Token swap through the pool
+    Swap,
+    /// Liquidity minted into a new position
+    Mint,
+    /// Liquidity burned from a position
+    Burn,
+    /// Fees or withdrawn tokens collected
+    Collect,
+    /// Liquidity added to an existing position
+    IncreaseLiquidity,
+    /// Liquidity removed from an existing position
+    DecreaseLiquidity,
+    /// A kind this client does not know, kept verbatim
+    Unknown(String),
+}
+
+impl TransactionKind {
+    /// Canonical spelling of the kind
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Swap => "swap",
+            Self::Mint => "mint",
+            Self::Burn => "burn",
+            Self::Collect => "collect",
+            Self::IncreaseLiquidity => "increaseLiquidity",
+            Self::DecreaseLiquidity => "decreaseLiquidity",
+            Self::Unknown(kind) => kind,
+        }
+    }
+}
+
+impl From<String> for TransactionKind {
+    /// Parse case-insensitively, ignoring `_`, `-` and spaces, so `INCREASE_LIQUIDITY`,
+    /// `increaseLiquidity` and `add-liquidity` are all recognized
+    fn from(kind: String) -> Self {
+        let key: String = kind
+            .chars()
+            .filter(|c| !matches!(c, '_' | '-' | ' '))
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match key.as_str() {
+            "swap" => Self::Swap,
+            "mint" => Self::Mint,
+            "burn" => Self::Burn,
+            "collect" | "collectfee" | "collectfees" => Self::Collect,
+            "increaseliquidity" | "addliquidity" => Self::IncreaseLiquidity,
+            "decreaseliquidity" | "removeliquidity" => Self::DecreaseLiquidity,
+            _ => Self::Unknown(kind),
+        }
+    }
+}
+
+impl From<TransactionKind> for String {
+    fn from(kind: TransactionKind) -> Self {
+        match kind {
+            TransactionKind::Unknown(kind) => kind,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for TransactionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
 impl Pool {
     /// Calculate volume-to-TVL ratio
     pub fn volume_tvl_ratio(&self) -> f64 {
@@ -449,6 +715,16 @@ impl Pool {
     pub fn apr(&self) -> Option<f64> {
         self.stats24h.as_ref().map(|s| s.apr)
     }
+
+    /// Value this pool is ranked by for a sort criterion (higher ranks first)
+    pub fn sort_value(&self, sort_by: PoolSortBy) -> f64 {
+        match sort_by {
+            PoolSortBy::Apr => self.apr().unwrap_or(0.0),
+            PoolSortBy::Tvl => self.tvl,
+            PoolSortBy::Volume24h => self.volume_24h(),
+            PoolSortBy::Fee => self.stats24h.as_ref().map_or(0.0, |s| s.fee),
+        }
+    }
 }
 
 impl Position {
@@ -464,12 +740,25 @@ impl Position {
 
     /// Check if position is active
     pub fn is_active(&self) -> bool {
-        self.status.to_uppercase() == "IN_RANGE" || self.status.to_uppercase() == "OUT_RANGE"
+        self.status.is_open()
     }
 
     /// Check if position is closed
     pub fn is_closed(&self) -> bool {
-        self.status.to_uppercase() == "CLOSED"
+        self.status == PositionRangeStatus::Closed
+    }
+
+    /// `{NFPM}-{TokenID}` identifier built from `token_address` and `token_id`, as accepted by
+    /// [`KrystalApiClient::get_position_detail`](crate::KrystalApiClient::get_position_detail)
+    pub fn position_id(&self) -> Result<PositionId, KrystalApiError> {
+        if !self.token_address.is_valid() {
+            return Err(KrystalApiError::InvalidParams(format!(
+                "Position {} has an invalid token address '{}'",
+                self.id,
+                self.token_address.as_str()
+            )));
+        }
+        PositionId::new(self.token_address.clone(), &self.token_id)
     }
 }
 
@@ -487,6 +776,14 @@ impl Transaction {
     pub fn is_recent(&self) -> bool {
         self.age_seconds() < 3600
     }
+
+    /// Token pair as `SYM0/SYM1`, when the token references were returned
+    pub fn pair_symbol(&self) -> Option<String> {
+        match (&self.token0, &self.token1) {
+            (Some(t0), Some(t1)) => Some(format!("{}/{}", t0.symbol, t1.symbol)),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -616,6 +913,8 @@ mod tests {
         assert_eq!(pool.apr(), Some(10.0));
         assert_eq!(pool.volume_tvl_ratio(), 0.1);
         assert!(pool.is_high_activity());
+        assert_eq!(pool.sort_value(PoolSortBy::Tvl), 10000.0);
+        assert_eq!(pool.sort_value(PoolSortBy::Fee), 10.0);
     }
 
     #[test]
@@ -642,4 +941,142 @@ mod tests {
         assert_eq!(stats.name.as_deref(), Some("Ethereum"));
         assert!(stats.additional_fields.contains_key("tvl"));
     }
+
+    #[test]
+    fn test_token_with_value_keeps_exact_balance() {
+        let json = r#"{
+            "token": {"address": "0x1", "symbol": "WETH", "name": "Wrapped Ether", "decimals": 18},
+            "balance": "1234567890123456789012",
+            "price": 2000.0,
+            "value": 2469135.78
+        }"#;
+        let amount: TokenWithValue = serde_json::from_str(json).unwrap();
+
+        assert_eq!(amount.balance.to_string(), "1234.567890123456789012");
+        assert!((amount.balance_usd() - 2_469_135.78).abs() < 0.01);
+
+        let round_trip = serde_json::to_value(&amount).unwrap();
+        assert_eq!(round_trip["balance"], "1234567890123456789012");
+
+        let units = json.replace("1234567890123456789012", "1.5");
+        assert!(serde_json::from_str::<TokenWithValue>(&units).is_err());
+    }
+
+    #[test]
+    fn test_transaction_amounts_from_numbers_and_strings() {
+        let json = r#"{"hash": "0xa", "timestamp": 1, "type": "swap",
+            "amount0": -0.1, "amount1": "123456789.123456789123456789"}"#;
+        let tx: Transaction = serde_json::from_str(json).unwrap();
+
+        assert_eq!(tx.amount0.to_string(), "-0.1");
+        assert_eq!(tx.amount1.to_string(), "123456789.123456789123456789");
+        assert_eq!(tx.amount0.decimals(), 1);
+
+        let round_trip = serde_json::to_value(&tx).unwrap();
+        assert_eq!(round_trip["amount0"], -0.1);
+        assert!(round_trip["amount1"].is_number());
+    }
+
+    #[test]
+    fn test_transaction_amounts_use_token_decimals() {
+        let json = r#"{"hash": "0xa", "timestamp": 1, "type": "swap",
+            "amount0": 1.5, "amount1": -2999.1234567,
+            "token0": {"address": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                "symbol": "WETH", "name": "Wrapped Ether", "decimals": 18},
+            "token1": {"address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "symbol": "USDC", "name": "USD Coin", "decimals": 6}}"#;
+        let tx: Transaction = serde_json::from_str(json).unwrap();
+
+        assert_eq!(tx.amount0.decimals(), 18);
+        assert_eq!(tx.amount0.raw().to_string(), "1500000000000000000");
+        assert_eq!(tx.amount1.decimals(), 6);
+        assert_eq!(tx.amount1.to_string(), "-2999.123457");
+    }
+
+    #[test]
+    fn test_transaction_participants_and_block_data() {
+        let json = r#"{"hash": "0xa", "timestamp": 1, "type": "swap", "amount0": "-1.5",
+            "amount1": "3000", "sender": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "recipient": "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359", "blockNumber": 19000000,
+            "logIndex": 42, "valueUsd": 3001.25,
+            "token0": {"address": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                "symbol": "WETH", "name": "Wrapped Ether", "decimals": 18},
+            "token1": {"address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "symbol": "USDC", "name": "USD Coin", "decimals": 6}}"#;
+        let tx: Transaction = serde_json::from_str(json).unwrap();
+
+        assert_eq!(tx.sender.as_ref().unwrap(), "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+        assert_eq!(tx.recipient.as_ref().unwrap(), "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359");
+        assert_eq!((tx.block_number, tx.log_index), (Some(19_000_000), Some(42)));
+        assert_eq!(tx.value_usd, Some(3001.25));
+        assert_eq!(tx.pair_symbol().as_deref(), Some("WETH/USDC"));
+        assert!(tx.additional_fields.is_empty());
+
+        let minimal: Transaction = serde_json::from_str(
+            r#"{"hash": "0xb", "timestamp": 1, "type": "mint", "amount0": 0, "amount1": 0}"#,
+        )
+        .unwrap();
+        assert_eq!((&minimal.sender, minimal.block_number), (&None, None));
+        assert_eq!(minimal.pair_symbol(), None);
+
+        let other_names: Transaction = serde_json::from_str(
+            r#"{"hash": "0xc", "timestamp": 1, "type": "swap", "amount0": 0, "amount1": 0,
+                "sender": "0x123", "from": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+                "amountUsd": 1.0}"#,
+        )
+        .unwrap();
+        assert!(!other_names.sender.as_ref().unwrap().is_valid());
+        assert_eq!(other_names.value_usd, None);
+        assert!(other_names.additional_fields.contains_key("from"));
+        assert!(other_names.additional_fields.contains_key("amountUsd"));
+    }
+
+    #[test]
+    fn test_position_range_status_serde() {
+        let parse = |s: &str| serde_json::from_value::<PositionRangeStatus>(s.into()).unwrap();
+
+        assert_eq!(parse("IN_RANGE"), PositionRangeStatus::InRange);
+        assert_eq!(parse("out_of_range"), PositionRangeStatus::OutOfRange);
+        assert_eq!(parse("Closed"), PositionRangeStatus::Closed);
+        assert!(parse("OUT_RANGE").is_open() && !parse("CLOSED").is_open());
+
+        let unknown = parse("PENDING");
+        assert_eq!(unknown, PositionRangeStatus::Unknown("PENDING".to_string()));
+        assert_eq!(serde_json::to_value(&unknown).unwrap(), "PENDING");
+        assert_eq!(serde_json::to_value(parse("in_range")).unwrap(), "IN_RANGE");
+    }
+
+    #[test]
+    fn test_transaction_kind_serde() {
+        let parse = |s: &str| serde_json::from_value::<TransactionKind>(s.into()).unwrap();
+
+        assert_eq!(parse("swap"), TransactionKind::Swap);
+        assert_eq!(parse("MINT"), TransactionKind::Mint);
+        assert_eq!(parse("INCREASE_LIQUIDITY"), TransactionKind::IncreaseLiquidity);
+        assert_eq!(parse("decreaseLiquidity"), TransactionKind::DecreaseLiquidity);
+        assert_eq!(parse("collect-fees"), TransactionKind::Collect);
+
+        let unknown = parse("flash");
+        assert_eq!(unknown, TransactionKind::Unknown("flash".to_string()));
+        assert_eq!(serde_json::to_value(&unknown).unwrap(), "flash");
+        assert_eq!(
+            serde_json::to_value(TransactionKind::IncreaseLiquidity).unwrap(),
+            "increaseLiquidity"
+        );
+    }
+
+    #[test]
+    fn test_position_links_to_its_position_id() {
+        let json = r#"{"id": "0xc36442b4a4522e871399cd717abdd847ab11fe88-1028436",
+            "ownerAddress": "0x742d35cc6639c0532Fa20C00fa1a5a6f1A8f3B82",
+            "tokenAddress": "0xC36442b4a4522E871399CD717aBDD847Ab11FE88", "tokenId": "1028436",
+            "liquidity": "1000", "minPrice": 1.0, "maxPrice": 2.0,
+            "currentPositionValue": 10.0, "status": "IN_RANGE"}"#;
+        let position: Position = serde_json::from_str(json).unwrap();
+
+        let id = position.position_id().unwrap();
+        assert_eq!(id.to_string(), position.id);
+        assert_eq!(id.nfpm(), &position.token_address);
+        assert_eq!(id.token_id(), "1028436");
+    }
 }
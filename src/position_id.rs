@@ -0,0 +1,130 @@
+// file: src/position_id.rs
+// description: Liquidity position identifier in the API's `{NFPM address}-{TokenID}` form
+// docs_reference: https://docs.krystal.app/krystal-cloud/api-reference
+
+use crate::address::Address;
+use crate::error::{KrystalApiError, Result};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A position ID: the position manager (NFPM) contract and the NFT token ID it minted.
+///
+/// Parses and displays as `{nfpm}-{token_id}`, e.g.
+/// `0xc36442b4a4522e871399cd717abdd847ab11fe88-1028436`. The address is stored lowercase and
+/// leading zeros are dropped from the token ID, so equal positions compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PositionId {
+    nfpm: Address,
+    token_id: String,
+}
+
+impl PositionId {
+    /// Build an ID from the position manager address and a decimal token ID
+    pub fn new(nfpm: Address, token_id: &str) -> Result<Self> {
+        let digits = token_id.trim();
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(KrystalApiError::InvalidParams(format!(
+                "Invalid token ID '{token_id}': expected a decimal integer"
+            )));
+        }
+        // Only ASCII digits remain, so this parse cannot fail
+        let value: BigUint = digits.parse().unwrap_or_default();
+        if value.bits() > 256 {
+            return Err(KrystalApiError::InvalidParams(format!(
+                "Invalid token ID '{token_id}': larger than a uint256"
+            )));
+        }
+        Ok(Self {
+            nfpm,
+            token_id: value.to_string(),
+        })
+    }
+
+    /// Position manager (NFPM) contract, sent as `tokenAddress`
+    pub fn nfpm(&self) -> &Address {
+        &self.nfpm
+    }
+
+    /// NFT token ID in decimal, sent as `tokenId`
+    pub fn token_id(&self) -> &str {
+        &self.token_id
+    }
+}
+
+impl fmt::Display for PositionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!("{}-{}", self.nfpm.as_str(), self.token_id))
+    }
+}
+
+impl FromStr for PositionId {
+    type Err = KrystalApiError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (nfpm, token_id) = s.trim().split_once('-').ok_or_else(|| {
+            KrystalApiError::InvalidParams(format!(
+                "Invalid position ID '{s}': expected {{NFPM address}}-{{token ID}}"
+            ))
+        })?;
+        Self::new(nfpm.parse()?, token_id)
+    }
+}
+
+impl TryFrom<String> for PositionId {
+    type Error = KrystalApiError;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<PositionId> for String {
+    fn from(id: PositionId) -> Self {
+        id.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NFPM: &str = "0xC36442b4a4522E871399CD717aBDD847Ab11FE88";
+
+    #[test]
+    fn test_position_id_round_trip() {
+        let id: PositionId = format!("{NFPM}-1028436").parse().unwrap();
+        assert_eq!(id.nfpm(), NFPM);
+        assert_eq!(id.token_id(), "1028436");
+        assert_eq!(id.to_string(), format!("{}-1028436", NFPM.to_lowercase()));
+
+        let padded: PositionId = format!("{}-0001028436", NFPM.to_lowercase())
+            .parse()
+            .unwrap();
+        assert_eq!(padded, id);
+        assert_eq!(
+            PositionId::new(Address::zero(), "000").unwrap().token_id(),
+            "0"
+        );
+
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(serde_json::from_str::<PositionId>(&json).unwrap(), id);
+    }
+
+    #[test]
+    fn test_position_id_rejects_malformed_input() {
+        for bad in [
+            NFPM.to_string(),
+            format!("{NFPM}-"),
+            format!("{NFPM}-12a"),
+            format!("{NFPM}--1"),
+            format!("{NFPM}-{}", "9".repeat(78)),
+            "0x123-1".to_string(),
+            "42".to_string(),
+        ] {
+            assert!(bad.parse::<PositionId>().is_err(), "{bad} should not parse");
+        }
+    }
+}
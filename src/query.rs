@@ -10,6 +10,8 @@ use crate::models::{PoolSortBy, PositionStatus};
 pub struct PoolsQuery {
     /// Filter by specific chain ID
     pub chain_id: Option<u32>,
+    /// Query several chains at once; results are merged, re-sorted and limited globally
+    pub chain_ids: Vec<u32>,
     /// Filter by factory contract address
     pub factory_address: Option<String>,
     /// Filter by protocol name
@@ -31,6 +33,9 @@ pub struct PoolsQuery {
 }
 
 impl PoolsQuery {
+    /// Largest `limit` the pools endpoint accepts
+    pub const MAX_LIMIT: u32 = 1000;
+
     /// Create a new empty query builder
     pub fn new() -> Self {
         Self::default()
@@ -42,6 +47,17 @@ impl PoolsQuery {
         self
     }
 
+    /// Query several chains concurrently and merge the results
+    pub fn chain_ids<I: IntoIterator<Item = u32>>(mut self, chain_ids: I) -> Self {
+        self.chain_ids = chain_ids.into_iter().collect();
+        self
+    }
+
+    /// Whether this query fans out across several chains
+    pub fn is_multi_chain(&self) -> bool {
+        !self.chain_ids.is_empty()
+    }
+
     /// Set factory address filter
     pub fn factory_address<S: Into<String>>(mut self, address: S) -> Self {
         self.factory_address = Some(address.into());
@@ -99,9 +115,13 @@ impl PoolsQuery {
     /// Validate query parameters
     pub fn validate(&self) -> Result<(), String> {
         if let Some(limit) = self.limit
-            && (limit == 0 || limit > 1000)
+            && (limit == 0 || limit > Self::MAX_LIMIT)
         {
-            return Err("Limit must be between 1 and 1000".to_string());
+            return Err(format!("Limit must be between 1 and {}", Self::MAX_LIMIT));
+        }
+
+        if self.chain_id.is_some() && self.is_multi_chain() {
+            return Err("Set either chain_id or chain_ids, not both".to_string());
         }
 
         if let Some(tvl) = self.min_tvl
@@ -282,6 +302,16 @@ mod tests {
         assert!(valid_query.validate().is_ok());
     }
 
+    #[test]
+    fn test_pools_query_multi_chain() {
+        let query = PoolsQuery::new().chain_ids([1, 8453, 56]);
+        assert!(query.is_multi_chain());
+        assert_eq!(query.chain_ids, vec![1, 8453, 56]);
+        assert!(query.validate().is_ok());
+
+        assert!(query.chain_id(1).validate().is_err());
+    }
+
     #[test]
     fn test_positions_query_builder() {
         let query = PositionsQuery::new("0x742d35Cc6639C0532fA20c00fa1A5a6f1a8f3b82")
@@ -0,0 +1,312 @@
+// file: src/schema.rs
+// description: Schema drift detection for API models, reporting unknown keys captured in
+//             `additional_fields` and expected optional fields the API stopped sending
+// docs_reference: https://serde.rs/attr-flatten.html
+
+use crate::models::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// How the client treats response fields the models do not know about
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Keep unknown fields in `additional_fields` (default)
+    #[default]
+    Lenient,
+    /// Fail with [`crate::KrystalApiError::SchemaDrift`] when any unknown field appears
+    Strict,
+}
+
+/// A model with a flattened `additional_fields` map that can be checked for drift
+pub trait SchemaModel {
+    /// Model name used in drift reports
+    const NAME: &'static str;
+
+    /// Fields the API sent that the model does not declare
+    fn additional_fields(&self) -> &HashMap<String, Value>;
+
+    /// Expected optional fields (API names) that were absent or null
+    fn missing_fields(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Record this model, and any nested models, in a drift report
+    fn observe(&self, report: &mut SchemaReport) {
+        report.record(
+            Self::NAME,
+            self.additional_fields().keys(),
+            self.missing_fields(),
+        );
+    }
+}
+
+/// Drift seen for one model across the samples checked
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct ModelDrift {
+    /// Number of records checked
+    pub samples: usize,
+    /// Unknown key -> number of records it appeared in
+    pub unknown_fields: BTreeMap<String, usize>,
+    /// Missing optional field -> number of records it was absent from
+    pub missing_fields: BTreeMap<String, usize>,
+}
+
+/// Per-model schema drift collected from parsed responses
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct SchemaReport {
+    /// Drift per model name
+    pub models: BTreeMap<String, ModelDrift>,
+}
+
+impl SchemaReport {
+    /// Create an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check a batch of parsed records
+    pub fn observe_all<T: SchemaModel>(&mut self, items: &[T]) {
+        for item in items {
+            item.observe(self);
+        }
+    }
+
+    /// Count one record of a model
+    pub fn record<'a, I>(&mut self, model: &str, unknown: I, missing: Vec<&'static str>)
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let drift = self.models.entry(model.to_string()).or_default();
+        drift.samples += 1;
+        for key in unknown {
+            *drift.unknown_fields.entry(key.clone()).or_default() += 1;
+        }
+        for field in missing {
+            *drift.missing_fields.entry(field.to_string()).or_default() += 1;
+        }
+    }
+
+    /// Whether any model saw unknown fields
+    pub fn has_unknown_fields(&self) -> bool {
+        self.models
+            .values()
+            .any(|drift| !drift.unknown_fields.is_empty())
+    }
+
+    /// Unknown fields as `Model.key`, sorted
+    pub fn unknown_fields(&self) -> Vec<String> {
+        self.models
+            .iter()
+            .flat_map(|(model, drift)| {
+                drift
+                    .unknown_fields
+                    .keys()
+                    .map(move |key| format!("{model}.{key}"))
+            })
+            .collect()
+    }
+}
+
+/// Names of the fields whose flag is set
+fn missing(fields: &[(&'static str, bool)]) -> Vec<&'static str> {
+    fields
+        .iter()
+        .filter(|(_, is_missing)| *is_missing)
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+impl SchemaModel for ChainInfo {
+    const NAME: &'static str = "ChainInfo";
+
+    fn additional_fields(&self) -> &HashMap<String, Value> {
+        &self.additional_fields
+    }
+
+    fn missing_fields(&self) -> Vec<&'static str> {
+        missing(&[
+            ("logo", self.logo.is_none()),
+            ("explorer", self.explorer.is_none()),
+        ])
+    }
+}
+
+impl SchemaModel for ChainStats {
+    const NAME: &'static str = "ChainStats";
+
+    fn additional_fields(&self) -> &HashMap<String, Value> {
+        &self.additional_fields
+    }
+
+    fn missing_fields(&self) -> Vec<&'static str> {
+        missing(&[("id", self.id.is_none()), ("name", self.name.is_none())])
+    }
+}
+
+impl SchemaModel for ProtocolSummary {
+    const NAME: &'static str = "ProtocolSummary";
+
+    fn additional_fields(&self) -> &HashMap<String, Value> {
+        &self.additional_fields
+    }
+
+    fn missing_fields(&self) -> Vec<&'static str> {
+        missing(&[
+            ("factoryAddress", self.factory_address.is_none()),
+            ("logo", self.logo.is_none()),
+        ])
+    }
+}
+
+impl SchemaModel for PoolHistoricalData {
+    const NAME: &'static str = "PoolHistoricalData";
+
+    fn additional_fields(&self) -> &HashMap<String, Value> {
+        &self.additional_fields
+    }
+
+    fn missing_fields(&self) -> Vec<&'static str> {
+        missing(&[
+            ("data1h", self.data_1h.is_none()),
+            ("data1d", self.data_1d.is_none()),
+            ("data7d", self.data_7d.is_none()),
+        ])
+    }
+
+    fn observe(&self, report: &mut SchemaReport) {
+        report.record(Self::NAME, self.additional_fields.keys(), self.missing_fields());
+        for series in [&self.data_1h, &self.data_1d, &self.data_7d]
+            .into_iter()
+            .flatten()
+        {
+            report.observe_all(series);
+        }
+    }
+}
+
+impl SchemaModel for PoolHistoricalPoint {
+    const NAME: &'static str = "PoolHistoricalPoint";
+
+    fn additional_fields(&self) -> &HashMap<String, Value> {
+        &self.additional_fields
+    }
+
+    fn missing_fields(&self) -> Vec<&'static str> {
+        missing(&[
+            ("timestamp", self.timestamp.is_none()),
+            ("volume", self.volume.is_none()),
+            ("fee", self.fee.is_none()),
+            ("apr", self.apr.is_none()),
+            ("tvl", self.tvl.is_none()),
+        ])
+    }
+}
+
+impl SchemaModel for Pool {
+    const NAME: &'static str = "Pool";
+
+    fn additional_fields(&self) -> &HashMap<String, Value> {
+        &self.additional_fields
+    }
+
+    fn missing_fields(&self) -> Vec<&'static str> {
+        missing(&[
+            ("chain", self.chain.is_none()),
+            ("protocol", self.protocol.is_none()),
+            ("token0", self.token0.is_none()),
+            ("token1", self.token1.is_none()),
+            ("stats1h", self.stats1h.is_none()),
+            ("stats24h", self.stats24h.is_none()),
+            ("stats7d", self.stats7d.is_none()),
+            ("stats30d", self.stats30d.is_none()),
+            ("incentives", self.incentives.is_none()),
+        ])
+    }
+
+    fn observe(&self, report: &mut SchemaReport) {
+        report.record(Self::NAME, self.additional_fields.keys(), self.missing_fields());
+        if let Some(chain) = &self.chain {
+            chain.observe(report);
+        }
+    }
+}
+
+impl SchemaModel for Position {
+    const NAME: &'static str = "Position";
+
+    fn additional_fields(&self) -> &HashMap<String, Value> {
+        &self.additional_fields
+    }
+
+    fn missing_fields(&self) -> Vec<&'static str> {
+        missing(&[
+            ("chain", self.chain.is_none()),
+            ("pool", self.pool.is_none()),
+            ("currentAmounts", self.current_amounts.is_none()),
+            ("providedAmounts", self.provided_amounts.is_none()),
+            ("tradingFee", self.trading_fee.is_none()),
+            ("farmingReward", self.farming_reward.is_none()),
+            ("performance", self.performance.is_none()),
+        ])
+    }
+
+    fn observe(&self, report: &mut SchemaReport) {
+        report.record(Self::NAME, self.additional_fields.keys(), self.missing_fields());
+        if let Some(chain) = &self.chain {
+            chain.observe(report);
+        }
+    }
+}
+
+impl SchemaModel for Transaction {
+    const NAME: &'static str = "Transaction";
+
+    fn additional_fields(&self) -> &HashMap<String, Value> {
+        &self.additional_fields
+    }
+
+    fn missing_fields(&self) -> Vec<&'static str> {
+        missing(&[
+            ("sender", self.sender.is_none()),
+            ("recipient", self.recipient.is_none()),
+            ("blockNumber", self.block_number.is_none()),
+            ("logIndex", self.log_index.is_none()),
+            ("valueUsd", self.value_usd.is_none()),
+            ("token0", self.token0.is_none()),
+            ("token1", self.token1.is_none()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_report_counts_unknown_and_missing_fields() {
+        let pools: Vec<Pool> = serde_json::from_value(json!([
+            {
+                "poolAddress": "0x0000000000000000000000000000000000000001",
+                "poolPrice": 1.0, "feeTier": 500, "tvl": 1.0,
+                "chain": {"id": 1, "name": "Ethereum", "logo": "l", "explorer": "e", "rpc": "x"},
+                "poolType": "v3"
+            },
+            {"poolAddress": "0x0000000000000000000000000000000000000002",
+                "poolPrice": 1.0, "feeTier": 500, "tvl": 1.0, "poolType": "v2"}
+        ]))
+        .unwrap();
+
+        let mut report = SchemaReport::new();
+        report.observe_all(&pools);
+
+        let pool = &report.models["Pool"];
+        assert_eq!(pool.samples, 2);
+        assert_eq!(pool.unknown_fields["poolType"], 2);
+        assert_eq!(pool.missing_fields["chain"], 1);
+        assert_eq!(pool.missing_fields["stats24h"], 2);
+        assert_eq!(report.models["ChainInfo"].unknown_fields["rpc"], 1);
+        assert_eq!(report.unknown_fields(), ["ChainInfo.rpc", "Pool.poolType"]);
+    }
+}
@@ -0,0 +1,424 @@
+// file: src/spec.rs
+// description: Conformance checks of request URLs against the bundled Swagger spec (`api.json`),
+//             covering documented paths, query parameter names, types and limits
+// docs_reference: https://swagger.io/specification/v2/
+
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::OnceLock;
+use url::Url;
+
+/// The Swagger spec shipped with the crate
+const BUNDLED_SPEC: &str = include_str!("../api.json");
+
+/// How the client treats requests that do not match the API spec
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpecCheck {
+    /// Do not check requests (default)
+    #[default]
+    Off,
+    /// Log mismatches as warnings and send the request anyway
+    Warn,
+    /// Fail with [`crate::KrystalApiError::SpecViolation`] before sending the request
+    Strict,
+}
+
+/// Declared type of a parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamType {
+    Integer,
+    Number,
+    Boolean,
+    String,
+    Array,
+}
+
+impl ParamType {
+    fn from_swagger(kind: Option<&str>) -> Self {
+        match kind {
+            Some("integer") => Self::Integer,
+            Some("number") => Self::Number,
+            Some("boolean") => Self::Boolean,
+            Some("array") => Self::Array,
+            _ => Self::String,
+        }
+    }
+
+    /// Whether a raw parameter value is valid for this type
+    fn accepts(self, value: &str) -> bool {
+        match self {
+            Self::Integer => value.parse::<i64>().is_ok(),
+            Self::Number => value.parse::<f64>().is_ok(),
+            Self::Boolean => matches!(value, "true" | "false"),
+            Self::String | Self::Array => true,
+        }
+    }
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::String => "string",
+            Self::Array => "array",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A documented parameter of an operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamSpec {
+    /// Parameter name as sent on the wire
+    pub name: String,
+    /// Whether the parameter is a `{placeholder}` in the path rather than a query parameter
+    pub in_path: bool,
+    /// Declared type
+    pub kind: ParamType,
+    /// Whether the parameter must be present
+    pub required: bool,
+    /// Upper bound, from `maximum` or a "(max N)" note in the description
+    pub maximum: Option<u64>,
+}
+
+/// A documented GET operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operation {
+    /// Path template, e.g. `/v1/pools/{chainId}/{poolAddress}`
+    pub path: String,
+    /// Parameters, or `None` when the spec documents none for this operation
+    pub params: Option<Vec<ParamSpec>>,
+}
+
+impl Operation {
+    /// Path placeholder values when `path` matches the template
+    fn match_path<'p>(&self, path: &'p str) -> Option<HashMap<&str, &'p str>> {
+        let template: Vec<&str> = self.path.trim_matches('/').split('/').collect();
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        if template.len() != segments.len() {
+            return None;
+        }
+
+        let mut values = HashMap::new();
+        for (expected, actual) in template.iter().zip(segments) {
+            match expected.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                Some(name) if !actual.is_empty() => {
+                    values.insert(name, actual);
+                }
+                None if *expected == actual => {}
+                _ => return None,
+            }
+        }
+        Some(values)
+    }
+
+    /// Number of literal (non-placeholder) segments; more specific templates win
+    fn literal_segments(&self) -> usize {
+        self.path.split('/').filter(|s| !s.starts_with('{')).count()
+    }
+}
+
+/// A mismatch between a request and the spec
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecViolation {
+    /// The path matches no documented operation
+    UnknownPath(String),
+    /// A query parameter the operation does not declare
+    UnknownParam(String),
+    /// A required parameter is absent
+    MissingParam(String),
+    /// A non-array parameter was sent more than once
+    RepeatedParam(String),
+    /// A value does not parse as the declared type
+    InvalidValue {
+        name: String,
+        value: String,
+        expected: ParamType,
+    },
+    /// A value exceeds the documented maximum
+    AboveMaximum {
+        name: String,
+        value: u64,
+        maximum: u64,
+    },
+}
+
+impl fmt::Display for SpecViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownPath(path) => write!(f, "undocumented path {path}"),
+            Self::UnknownParam(name) => write!(f, "undocumented parameter '{name}'"),
+            Self::MissingParam(name) => write!(f, "missing required parameter '{name}'"),
+            Self::RepeatedParam(name) => write!(f, "parameter '{name}' sent more than once"),
+            Self::InvalidValue {
+                name,
+                value,
+                expected,
+            } => write!(f, "parameter '{name}' = '{value}' is not a valid {expected}"),
+            Self::AboveMaximum {
+                name,
+                value,
+                maximum,
+            } => write!(f, "parameter '{name}' = {value} exceeds the maximum of {maximum}"),
+        }
+    }
+}
+
+/// GET operations of a Swagger 2.0 spec, used to check request URLs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiSpec {
+    base_path: String,
+    operations: Vec<Operation>,
+}
+
+#[derive(Deserialize)]
+struct RawSpec {
+    #[serde(rename = "basePath", default)]
+    base_path: Option<String>,
+    paths: BTreeMap<String, BTreeMap<String, RawOperation>>,
+}
+
+#[derive(Deserialize)]
+struct RawOperation {
+    parameters: Option<Vec<RawParam>>,
+}
+
+#[derive(Deserialize)]
+struct RawParam {
+    name: String,
+    #[serde(rename = "in")]
+    location: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    required: bool,
+    maximum: Option<u64>,
+    description: Option<String>,
+}
+
+impl ApiSpec {
+    /// The spec bundled with the crate, parsed once
+    pub fn bundled() -> &'static ApiSpec {
+        static SPEC: OnceLock<ApiSpec> = OnceLock::new();
+        SPEC.get_or_init(|| Self::from_json(BUNDLED_SPEC).expect("bundled api.json is valid"))
+    }
+
+    /// Parse a Swagger 2.0 document; only GET operations are kept
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let raw: RawSpec = serde_json::from_str(json)?;
+
+        let operations = raw
+            .paths
+            .into_iter()
+            .filter_map(|(path, mut methods)| {
+                let get = methods.remove("get")?;
+                let params = get.parameters.map(|params| {
+                    params
+                        .into_iter()
+                        .map(|param| ParamSpec {
+                            // The spec marks some path placeholders as query parameters
+                            in_path: param.location == "path"
+                                || path.contains(&format!("{{{}}}", param.name)),
+                            kind: ParamType::from_swagger(param.kind.as_deref()),
+                            required: param.required,
+                            maximum: param
+                                .maximum
+                                .or_else(|| param.description.as_deref().and_then(max_note)),
+                            name: param.name,
+                        })
+                        .collect()
+                });
+                Some(Operation { path, params })
+            })
+            .collect();
+
+        Ok(Self {
+            base_path: raw.base_path.unwrap_or_else(|| "/".to_string()),
+            operations,
+        })
+    }
+
+    /// Documented GET operations
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// The most specific operation whose template matches an API path such as `/v1/chains/1`
+    pub fn operation(&self, path: &str) -> Option<&Operation> {
+        self.operations
+            .iter()
+            .filter(|op| op.match_path(path).is_some())
+            .max_by_key(|op| op.literal_segments())
+    }
+
+    /// Check a request URL; `api_root` is the client's base URL, which may include a path
+    /// prefix in front of the spec's paths
+    pub fn check_url(&self, url: &Url, api_root: &Url) -> Vec<SpecViolation> {
+        let relative = url
+            .path()
+            .strip_prefix(api_root.path().trim_end_matches('/'))
+            .unwrap_or(url.path());
+        let path = match self.base_path.trim_end_matches('/') {
+            "" => relative,
+            base => relative.strip_prefix(base).unwrap_or(relative),
+        };
+        let params: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+        self.check(path, &params)
+    }
+
+    /// Check an API path and its query parameters
+    pub fn check(&self, path: &str, params: &[(String, String)]) -> Vec<SpecViolation> {
+        let Some(operation) = self.operation(path) else {
+            return vec![SpecViolation::UnknownPath(path.to_string())];
+        };
+        // Operations without documented parameters cannot be checked further
+        let Some(declared) = &operation.params else {
+            return Vec::new();
+        };
+
+        let placeholders = operation.match_path(path).unwrap_or_default();
+        let mut violations = Vec::new();
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+
+        for (name, value) in params {
+            let Some(spec) = declared.iter().find(|p| !p.in_path && p.name == *name) else {
+                violations.push(SpecViolation::UnknownParam(name.clone()));
+                continue;
+            };
+            let count = seen.entry(spec.name.as_str()).or_default();
+            *count += 1;
+            if *count == 2 && spec.kind != ParamType::Array {
+                violations.push(SpecViolation::RepeatedParam(name.clone()));
+            }
+            violations.extend(check_value(spec, value));
+        }
+
+        for spec in declared {
+            if spec.in_path {
+                if let Some(value) = placeholders.get(spec.name.as_str()) {
+                    violations.extend(check_value(spec, value));
+                }
+            } else if spec.required && !seen.contains_key(spec.name.as_str()) {
+                violations.push(SpecViolation::MissingParam(spec.name.clone()));
+            }
+        }
+
+        violations
+    }
+}
+
+fn check_value(spec: &ParamSpec, value: &str) -> Option<SpecViolation> {
+    if !spec.kind.accepts(value) {
+        return Some(SpecViolation::InvalidValue {
+            name: spec.name.clone(),
+            value: value.to_string(),
+            expected: spec.kind,
+        });
+    }
+
+    let maximum = spec.maximum?;
+    let value = value.parse::<u64>().ok()?;
+    (value > maximum).then(|| SpecViolation::AboveMaximum {
+        name: spec.name.clone(),
+        value,
+        maximum,
+    })
+}
+
+/// Limit stated in a description such as "Number of results to return (max 5000)"
+fn max_note(description: &str) -> Option<u64> {
+    let lower = description.to_ascii_lowercase();
+    let rest = &lower[lower.find("max ")? + 4..];
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(params: &[(&str, &str)]) -> Vec<(String, String)> {
+        params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_bundled_spec_operations_and_limits() {
+        let spec = ApiSpec::bundled();
+        assert_eq!(spec.operations().len(), 10);
+
+        let op = spec.operation("/v1/positions/1/transactions").unwrap();
+        assert_eq!(op.path, "/v1/positions/{chainId}/transactions");
+
+        let op = spec.operation("/v1/pools/1/0xabc/transactions").unwrap();
+        let limit = op.params.as_ref().unwrap().iter().find(|p| p.name == "limit");
+        assert_eq!(limit.unwrap().maximum, Some(5000));
+        let chain_id = op.params.as_ref().unwrap().iter().find(|p| p.name == "chainId");
+        assert!(chain_id.unwrap().in_path);
+
+        assert_eq!(max_note("Results (max 5000)"), Some(5000));
+        assert_eq!(max_note("Pool address"), None);
+    }
+
+    #[test]
+    fn test_check_reports_mismatches() {
+        let spec = ApiSpec::bundled();
+        let path = "/v1/pools/1/0xabc/transactions";
+
+        let ok = pairs(&[("startTime", "1"), ("endTime", "2"), ("limit", "5000")]);
+        assert!(spec.check(path, &ok).is_empty());
+
+        let bad = pairs(&[("startTimestamp", "1"), ("limit", "5001"), ("offset", "x")]);
+        assert_eq!(
+            spec.check(path, &bad),
+            [
+                SpecViolation::UnknownParam("startTimestamp".to_string()),
+                SpecViolation::AboveMaximum {
+                    name: "limit".to_string(),
+                    value: 5001,
+                    maximum: 5000
+                },
+                SpecViolation::InvalidValue {
+                    name: "offset".to_string(),
+                    value: "x".to_string(),
+                    expected: ParamType::Integer
+                },
+            ]
+        );
+
+        assert_eq!(
+            spec.check("/v1/positions", &pairs(&[("protocols", "a"), ("protocols", "b")])),
+            [SpecViolation::MissingParam("wallet".to_string())]
+        );
+        assert_eq!(
+            spec.check("/v1/positions/eth/0x1", &[]),
+            [SpecViolation::InvalidValue {
+                name: "chainId".to_string(),
+                value: "eth".to_string(),
+                expected: ParamType::Integer
+            }]
+        );
+        assert_eq!(
+            spec.check("/v1/fees", &[]),
+            [SpecViolation::UnknownPath("/v1/fees".to_string())]
+        );
+        // `/v1/pools` documents no parameters, so only the path is checked
+        assert!(spec.check("/v1/pools", &pairs(&[("chainId", "1")])).is_empty());
+    }
+
+    #[test]
+    fn test_check_url_strips_api_root() {
+        let spec = ApiSpec::bundled();
+        let root = Url::parse("https://proxy.test/krystal/").unwrap();
+        let url = Url::parse("https://proxy.test/krystal/v1/chains/1").unwrap();
+        assert!(spec.check_url(&url, &root).is_empty());
+    }
+}
@@ -0,0 +1,287 @@
+// file: src/transport.rs
+// description: Pluggable HTTP transport layer for the API client, providing the default
+//             reqwest-backed transport and an in-memory transport for offline testing
+// docs_reference: https://docs.rs/reqwest/latest/reqwest/
+
+use crate::client::ClientConfig;
+use crate::error::Result;
+use bytes::Bytes;
+use reqwest::{Certificate, Client, Proxy};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::time::Duration;
+use url::Url;
+
+/// Boxed future returned by [`HttpTransport::get`]
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<HttpResponse>> + Send + 'a>>;
+
+/// An outgoing GET request, fully resolved with URL and headers
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    /// Target URL including query parameters
+    pub url: Url,
+    /// Request headers (the API key header is marked sensitive)
+    pub headers: HeaderMap,
+    /// Timeout for this request, overriding the transport default
+    pub timeout: Option<Duration>,
+}
+
+impl HttpRequest {
+    /// Create a request for a URL with no headers
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            headers: HeaderMap::new(),
+            timeout: None,
+        }
+    }
+}
+
+/// A raw HTTP response as seen by the client before status handling
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers
+    pub headers: HeaderMap,
+    /// Raw response body
+    pub body: Bytes,
+}
+
+impl HttpResponse {
+    /// Create a response with a status code and raw body
+    pub fn new<B: Into<Bytes>>(status: u16, body: B) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Create a response with a JSON body
+    pub fn json(status: u16, value: &serde_json::Value) -> Self {
+        Self::new(status, value.to_string()).with_header("content-type", "application/json")
+    }
+
+    /// Add a header to the response, ignoring invalid names or values
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            self.headers.insert(name, value);
+        }
+        self
+    }
+
+    /// Body decoded as UTF-8 (lossy)
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// Abstraction over the HTTP layer used by [`crate::KrystalApiClient`].
+///
+/// Implementations only need to perform a single GET; rate limiting, retries and status
+/// handling stay in the client.
+pub trait HttpTransport: Send + Sync {
+    /// Perform a GET request and return the raw response
+    fn get(&self, request: HttpRequest) -> TransportFuture<'_>;
+}
+
+/// Default transport backed by `reqwest`
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    /// Build a transport from the client configuration (timeout, user agent, proxy, extra
+    /// root certificates); HTTPS only unless the base URL is an allowed plain-HTTP URL
+    pub fn new(config: &ClientConfig) -> Result<Self> {
+        let base_url = config.checked_base_url()?;
+        let mut builder = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent(&config.user_agent)
+            .https_only(base_url.scheme() == "https");
+
+        if let Some(proxy) = &config.proxy {
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+        if let Some(path) = &config.ca_cert {
+            let pem = std::fs::read(path)?;
+            builder = builder.tls_certs_merge(Certificate::from_pem_bundle(&pem)?);
+        }
+
+        Ok(Self {
+            client: builder.build()?,
+        })
+    }
+
+    /// Wrap a pre-configured `reqwest::Client`
+    pub fn from_client(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn get(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let mut builder = self.client.get(request.url).headers(request.headers);
+            if let Some(timeout) = request.timeout {
+                builder = builder.timeout(timeout);
+            }
+            let response = builder.send().await?;
+
+            let status = response.status().as_u16();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?;
+
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+/// In-memory transport serving canned responses by URL path.
+///
+/// Responses registered for a path are served in order; the last one is repeated for
+/// any further requests. Unknown paths get a 404. Every request is recorded so tests
+/// can assert on the URLs and query parameters the client produced.
+///
+/// ```rust
+/// use krystal_cli::transport::MockTransport;
+/// use krystal_cli::{ClientConfig, KrystalApiClient};
+/// use std::sync::Arc;
+///
+/// let transport = Arc::new(
+///     MockTransport::new().with_json("/v1/chains", serde_json::json!([{"id": 1, "name": "Ethereum"}])),
+/// );
+/// let client = KrystalApiClient::with_transport(
+///     "test-key".to_string(),
+///     ClientConfig::default(),
+///     transport.clone(),
+/// )
+/// .unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    routes: Mutex<HashMap<String, VecDeque<HttpResponse>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+    latency: Option<Duration>,
+}
+
+impl MockTransport {
+    /// Create an empty mock transport
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delay every response by a fixed latency (builder style)
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Register a 200 JSON response for a path (builder style)
+    pub fn with_json(self, path: &str, body: serde_json::Value) -> Self {
+        self.push_response(path, HttpResponse::json(200, &body));
+        self
+    }
+
+    /// Register an arbitrary response for a path (builder style)
+    pub fn with_response(self, path: &str, response: HttpResponse) -> Self {
+        self.push_response(path, response);
+        self
+    }
+
+    /// Queue a response for a path
+    pub fn push_response(&self, path: &str, response: HttpResponse) {
+        self.routes
+            .lock()
+            .expect("mock routes poisoned")
+            .entry(path.to_string())
+            .or_default()
+            .push_back(response);
+    }
+
+    /// All requests received so far, in order
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests
+            .lock()
+            .expect("mock requests poisoned")
+            .clone()
+    }
+
+    /// Number of requests received so far
+    pub fn request_count(&self) -> usize {
+        self.requests.lock().expect("mock requests poisoned").len()
+    }
+
+    fn next_response(&self, path: &str) -> HttpResponse {
+        let mut routes = self.routes.lock().expect("mock routes poisoned");
+        match routes.get_mut(path) {
+            Some(queue) if queue.len() > 1 => queue.pop_front().expect("queue is non-empty"),
+            Some(queue) if !queue.is_empty() => queue[0].clone(),
+            _ => HttpResponse::new(404, format!("no mock response registered for {path}")),
+        }
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn get(&self, request: HttpRequest) -> TransportFuture<'_> {
+        let response = self.next_response(request.url.path());
+        self.requests
+            .lock()
+            .expect("mock requests poisoned")
+            .push(request);
+        let latency = self.latency;
+        Box::pin(async move {
+            if let Some(latency) = latency {
+                tokio::time::sleep(latency).await;
+            }
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(path: &str) -> HttpRequest {
+        HttpRequest::new(Url::parse(&format!("https://api.example.com{path}")).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_mock_serves_queued_then_repeats_last() {
+        let mock = MockTransport::new()
+            .with_response("/v1/chains", HttpResponse::new(500, "boom"))
+            .with_json("/v1/chains", serde_json::json!([]));
+
+        assert_eq!(mock.get(request("/v1/chains")).await.unwrap().status, 500);
+        assert_eq!(mock.get(request("/v1/chains")).await.unwrap().status, 200);
+        assert_eq!(mock.get(request("/v1/chains")).await.unwrap().status, 200);
+        assert_eq!(mock.request_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_mock_unknown_path_returns_404() {
+        let mock = MockTransport::new();
+        let response = mock.get(request("/v1/unknown")).await.unwrap();
+        assert_eq!(response.status, 404);
+        assert!(response.text().contains("/v1/unknown"));
+    }
+
+    #[test]
+    fn test_response_with_header() {
+        let response = HttpResponse::new(429, "").with_header("Retry-After", "3");
+        assert_eq!(response.headers.get("retry-after").unwrap(), "3");
+    }
+}
@@ -0,0 +1,370 @@
+// file: src/usage.rs
+// description: Request and credit usage tracking, with in-process per-endpoint counters and a
+//             persistent JSON Lines ledger summarized by day or month
+// docs_reference: https://docs.krystal.app/krystal-cloud/api-reference
+
+use crate::error::Result;
+use crate::utils::time;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use url::Url;
+
+/// Normalized endpoint name for a URL: numeric and `0x` path segments become placeholders,
+/// e.g. `/v1/pools/{id}/{address}/transactions`
+pub fn endpoint_name(url: &Url) -> String {
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| {
+            segments
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| {
+                    if segment.chars().all(|c| c.is_ascii_digit()) {
+                        "{id}"
+                    } else if segment.starts_with("0x") {
+                        "{address}"
+                    } else {
+                        segment
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    format!("/{}", segments.join("/"))
+}
+
+/// Counters for a single endpoint
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct EndpointUsage {
+    /// HTTP requests sent (every retry attempt counts)
+    pub requests: u64,
+    /// Requests that failed at the transport level or returned a non-2xx status
+    pub errors: u64,
+    /// Total response body bytes received
+    pub bytes: u64,
+    /// Sum of request latencies in milliseconds
+    pub total_latency_ms: u64,
+    /// Slowest request in milliseconds
+    pub max_latency_ms: u64,
+    /// Response count by HTTP status (0 = transport error)
+    pub status_codes: BTreeMap<u16, u64>,
+}
+
+impl EndpointUsage {
+    /// Mean request latency in milliseconds
+    pub fn average_latency_ms(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.total_latency_ms as f64 / self.requests as f64
+        }
+    }
+}
+
+/// Usage counters collected by a client since creation (or the last reset)
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct UsageReport {
+    /// Unix timestamp when collection started
+    pub since: u64,
+    /// Counters keyed by normalized endpoint name
+    pub endpoints: BTreeMap<String, EndpointUsage>,
+    /// Requests sent per API key fingerprint
+    #[serde(default)]
+    pub keys: BTreeMap<String, u64>,
+}
+
+impl UsageReport {
+    /// Create an empty report starting now
+    pub fn new() -> Self {
+        Self {
+            since: time::current_timestamp(),
+            endpoints: BTreeMap::new(),
+            keys: BTreeMap::new(),
+        }
+    }
+
+    /// Record one request
+    pub fn record(&mut self, record: &UsageRecord) {
+        let usage = self.endpoints.entry(record.endpoint.clone()).or_default();
+        usage.requests += 1;
+        if !(200..300).contains(&record.status) {
+            usage.errors += 1;
+        }
+        usage.bytes += record.bytes;
+        usage.total_latency_ms += record.latency_ms;
+        usage.max_latency_ms = usage.max_latency_ms.max(record.latency_ms);
+        *usage.status_codes.entry(record.status).or_default() += 1;
+
+        if let Some(key) = &record.key {
+            *self.keys.entry(key.clone()).or_default() += 1;
+        }
+    }
+
+    /// Total requests across all endpoints
+    pub fn total_requests(&self) -> u64 {
+        self.endpoints.values().map(|usage| usage.requests).sum()
+    }
+
+    /// Total response bytes across all endpoints
+    pub fn total_bytes(&self) -> u64 {
+        self.endpoints.values().map(|usage| usage.bytes).sum()
+    }
+}
+
+/// A single request as written to the ledger
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct UsageRecord {
+    /// Unix timestamp of the request
+    pub timestamp: u64,
+    /// Normalized endpoint name
+    pub endpoint: String,
+    /// HTTP status (0 = transport error)
+    pub status: u16,
+    /// Response body size in bytes
+    pub bytes: u64,
+    /// Request latency in milliseconds
+    pub latency_ms: u64,
+    /// Fingerprint of the API key that sent the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+impl UsageRecord {
+    /// Create a record for a request that just completed
+    pub fn new(url: &Url, status: u16, bytes: usize, latency: Duration) -> Self {
+        Self {
+            timestamp: time::current_timestamp(),
+            endpoint: endpoint_name(url),
+            status,
+            bytes: bytes as u64,
+            latency_ms: latency.as_millis() as u64,
+            key: None,
+        }
+    }
+
+    /// Attach the fingerprint of the API key that sent the request
+    pub fn with_key(mut self, fingerprint: String) -> Self {
+        self.key = Some(fingerprint);
+        self
+    }
+}
+
+/// Granularity of a ledger summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsagePeriod {
+    /// One bucket per UTC day (`YYYY-MM-DD`)
+    Daily,
+    /// One bucket per UTC month (`YYYY-MM`)
+    Monthly,
+}
+
+impl UsagePeriod {
+    fn bucket(self, timestamp: u64) -> String {
+        let date = time::format_date(timestamp);
+        match self {
+            Self::Daily => date,
+            Self::Monthly => date[..7].to_string(),
+        }
+    }
+}
+
+/// Aggregated ledger counts for one period and endpoint
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct UsageSummaryRow {
+    /// Period label (`YYYY-MM-DD` or `YYYY-MM`)
+    pub period: String,
+    /// Normalized endpoint name
+    pub endpoint: String,
+    /// Requests sent
+    pub requests: u64,
+    /// Failed requests
+    pub errors: u64,
+    /// Response bytes received
+    pub bytes: u64,
+}
+
+/// Append-only JSON Lines file recording every request the client sends
+#[derive(Debug, Clone)]
+pub struct UsageLedger {
+    path: PathBuf,
+}
+
+impl UsageLedger {
+    /// Use a ledger file at a specific path
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Default ledger path: `$KRYSTAL_USAGE_LEDGER`, `$XDG_DATA_HOME/krystal-cli/usage.jsonl`,
+    /// `$HOME/.local/share/krystal-cli/usage.jsonl`, or the system temp dir as a last resort
+    pub fn default_path() -> PathBuf {
+        if let Ok(path) = std::env::var("KRYSTAL_USAGE_LEDGER") {
+            return PathBuf::from(path);
+        }
+        if let Ok(dir) = std::env::var("XDG_DATA_HOME") {
+            return Path::new(&dir).join("krystal-cli").join("usage.jsonl");
+        }
+        if let Ok(home) = std::env::var("HOME") {
+            return Path::new(&home)
+                .join(".local")
+                .join("share")
+                .join("krystal-cli")
+                .join("usage.jsonl");
+        }
+        std::env::temp_dir().join("krystal-cli-usage.jsonl")
+    }
+
+    /// Path of the ledger file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a record to the ledger
+    pub fn append(&self, record: &UsageRecord) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Read all records, skipping malformed lines; a missing ledger is empty
+    pub fn records(&self) -> Result<Vec<UsageRecord>> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut records = Vec::new();
+        for line in BufReader::new(file).lines() {
+            if let Ok(record) = serde_json::from_str(&line?) {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+
+    /// Summarize records at or after `since` by period and endpoint, newest period first
+    pub fn summarize(&self, period: UsagePeriod, since: u64) -> Result<Vec<UsageSummaryRow>> {
+        let mut buckets: BTreeMap<(String, String), UsageSummaryRow> = BTreeMap::new();
+
+        for record in self.records()? {
+            if record.timestamp < since {
+                continue;
+            }
+            let label = period.bucket(record.timestamp);
+            let row = buckets
+                .entry((label.clone(), record.endpoint.clone()))
+                .or_insert_with(|| UsageSummaryRow {
+                    period: label,
+                    endpoint: record.endpoint.clone(),
+                    requests: 0,
+                    errors: 0,
+                    bytes: 0,
+                });
+            row.requests += 1;
+            if !(200..300).contains(&record.status) {
+                row.errors += 1;
+            }
+            row.bytes += record.bytes;
+        }
+
+        let mut rows: Vec<UsageSummaryRow> = buckets.into_values().collect();
+        rows.sort_by(|a, b| b.period.cmp(&a.period).then(b.requests.cmp(&a.requests)));
+        Ok(rows)
+    }
+
+    /// Delete the ledger file
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_name_normalizes_ids() {
+        let url = Url::parse("https://x.test/v1/pools/1/0xabc/transactions?limit=5").unwrap();
+        assert_eq!(endpoint_name(&url), "/v1/pools/{id}/{address}/transactions");
+
+        let url = Url::parse("https://x.test/v1/chains").unwrap();
+        assert_eq!(endpoint_name(&url), "/v1/chains");
+    }
+
+    #[test]
+    fn test_report_counts_requests_and_errors() {
+        let url = Url::parse("https://x.test/v1/pools").unwrap();
+        let mut report = UsageReport::new();
+        report.record(&UsageRecord::new(&url, 200, 100, Duration::from_millis(10)));
+        report.record(&UsageRecord::new(&url, 503, 20, Duration::from_millis(30)));
+
+        let pools = &report.endpoints["/v1/pools"];
+        assert_eq!(pools.requests, 2);
+        assert_eq!(pools.errors, 1);
+        assert_eq!(pools.bytes, 120);
+        assert_eq!(pools.max_latency_ms, 30);
+        assert_eq!(pools.average_latency_ms(), 20.0);
+        assert_eq!(pools.status_codes[&503], 1);
+        assert_eq!(report.total_requests(), 2);
+    }
+
+    #[test]
+    fn test_ledger_append_and_summarize() {
+        let path =
+            std::env::temp_dir().join(format!("krystal-usage-{}/usage.jsonl", std::process::id()));
+        let ledger = UsageLedger::new(&path);
+        ledger.clear().unwrap();
+
+        let record = |timestamp, endpoint: &str, status| UsageRecord {
+            timestamp,
+            endpoint: endpoint.to_string(),
+            status,
+            bytes: 10,
+            latency_ms: 5,
+            key: None,
+        };
+        // 2024-01-31, 2024-02-01 (twice)
+        ledger
+            .append(&record(1_706_659_200, "/v1/pools", 200))
+            .unwrap();
+        ledger
+            .append(&record(1_706_745_600, "/v1/pools", 200))
+            .unwrap();
+        ledger
+            .append(&record(1_706_745_700, "/v1/pools", 402))
+            .unwrap();
+
+        let daily = ledger.summarize(UsagePeriod::Daily, 0).unwrap();
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[0].period, "2024-02-01");
+        assert_eq!(daily[0].requests, 2);
+        assert_eq!(daily[0].errors, 1);
+
+        let monthly = ledger.summarize(UsagePeriod::Monthly, 0).unwrap();
+        assert_eq!(monthly.len(), 2);
+        assert_eq!(monthly[1].period, "2024-01");
+
+        let recent = ledger.summarize(UsagePeriod::Daily, 1_706_745_600).unwrap();
+        assert_eq!(recent.len(), 1);
+
+        ledger.clear().unwrap();
+        assert!(ledger.records().unwrap().is_empty());
+    }
+}
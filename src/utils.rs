@@ -50,6 +50,22 @@ pub mod time {
         }
     }
 
+    /// Format a Unix timestamp as a UTC calendar date (`YYYY-MM-DD`)
+    pub fn format_date(timestamp: u64) -> String {
+        // Civil-from-days conversion (Howard Hinnant's algorithm)
+        let days = (timestamp / 86400) as i64 + 719_468;
+        let era = days.div_euclid(146_097);
+        let doe = days.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+
     /// Get start of day timestamp for a given number of days ago
     pub fn start_of_day_ago(days: u64) -> u64 {
         let timestamp = days_ago(days);
@@ -114,7 +130,7 @@ pub mod address {
 
     /// Minimal Keccak-256 implementation for EIP-55 — returns hex digest.
     /// Uses the Keccak sponge (not SHA3-256; Ethereum uses the pre-NIST variant).
-    fn keccak_hex(input: &str) -> String {
+    pub(crate) fn keccak_hex(input: &str) -> String {
         // Rate = 1088 bits = 136 bytes, capacity = 512 bits, output = 256 bits
         const RATE: usize = 136;
         const OUTPUT_BYTES: usize = 32;
@@ -165,9 +181,10 @@ pub mod address {
             0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
             0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
         ];
+        // Rotation offsets in the order lanes are visited by PI (not by lane index)
         const RHO: [u32; 24] = [
-            1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43,
-            25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+            1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14,
+            27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
         ];
         const PI: [usize; 24] = [
             10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4,
@@ -253,6 +270,19 @@ pub mod address {
     }
 }
 
+/// Stable hashing helpers for on-disk file names
+pub mod hash {
+    /// 64-bit FNV-1a hash; unlike `DefaultHasher` it is stable across toolchain versions
+    pub fn fnv1a_64(input: &str) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+
+        input.bytes().fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(PRIME)
+        })
+    }
+}
+
 /// Utility functions for working with financial data
 pub mod finance {
     /// Format USD amount with appropriate precision
@@ -312,6 +342,9 @@ pub mod finance {
 /// Retry utilities for handling transient errors
 pub mod retry {
     use super::*;
+    use std::collections::hash_map::RandomState;
+    use std::hash::BuildHasher;
+    use std::time::Instant;
     use tokio::time::sleep;
 
     /// Retry configuration
@@ -325,6 +358,10 @@ pub mod retry {
         pub backoff_multiplier: f64,
         /// Maximum delay between retries
         pub max_delay: Duration,
+        /// Random jitter applied to each delay, as a fraction of it (0.0 = none)
+        pub jitter: f64,
+        /// Total time budget for all attempts and waits (`None` = unbounded)
+        pub max_retry_time: Option<Duration>,
     }
 
     impl Default for RetryConfig {
@@ -334,16 +371,51 @@ pub mod retry {
                 base_delay: Duration::from_millis(500),
                 backoff_multiplier: 2.0,
                 max_delay: Duration::from_secs(30),
+                jitter: 0.2,
+                max_retry_time: Some(Duration::from_secs(60)),
             }
         }
     }
 
-    /// Retry a future with exponential backoff
+    /// Parse a `Retry-After` header value (delta-seconds or HTTP-date)
+    pub fn parse_retry_after(value: &str) -> Option<Duration> {
+        let value = value.trim();
+        if let Ok(secs) = value.parse::<u64>() {
+            return Some(Duration::from_secs(secs));
+        }
+
+        let date = httpdate::parse_http_date(value).ok()?;
+        Some(
+            date.duration_since(SystemTime::now())
+                .unwrap_or(Duration::ZERO),
+        )
+    }
+
+    /// Uniform random value in `[0, 1)` without pulling in an RNG crate
+    fn random_unit() -> f64 {
+        (RandomState::new().hash_one(Instant::now()) >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Spread a backoff delay by up to `±jitter` of its length
+    fn with_jitter(delay: Duration, jitter: f64) -> Duration {
+        if jitter <= 0.0 {
+            return delay;
+        }
+        let factor = 1.0 + jitter.min(1.0) * (2.0 * random_unit() - 1.0);
+        delay.mul_f64(factor.max(0.0))
+    }
+
+    /// Retry a future with exponential backoff.
+    ///
+    /// Errors carrying a server-requested wait (`Retry-After`) are retried after at least
+    /// that long; other retryable errors use jittered exponential backoff. Gives up early
+    /// when the next wait would exceed `max_retry_time`.
     pub async fn retry_with_backoff<T, F, Fut>(config: RetryConfig, operation: F) -> Result<T>
     where
         F: Fn() -> Fut,
         Fut: Future<Output = Result<T>>,
     {
+        let started = Instant::now();
         let mut attempt = 0;
         let mut delay = config.base_delay;
 
@@ -355,9 +427,24 @@ pub mod retry {
                 Err(e) if attempt >= config.max_attempts || !e.is_retryable() => {
                     return Err(e);
                 }
-                Err(_) => {
+                Err(e) => {
+                    let wait = match e.retry_after() {
+                        // Never retry earlier than the server asked; only jitter upwards
+                        Some(retry_after) => {
+                            retry_after
+                                + retry_after.mul_f64(config.jitter.max(0.0) * random_unit())
+                        }
+                        None => with_jitter(delay, config.jitter),
+                    };
+
+                    if let Some(budget) = config.max_retry_time
+                        && started.elapsed() + wait > budget
+                    {
+                        return Err(e);
+                    }
+
                     // Wait before retrying
-                    sleep(delay).await;
+                    sleep(wait).await;
 
                     // Exponential backoff
                     delay = std::cmp::min(
@@ -382,6 +469,8 @@ pub mod retry {
             base_delay: Duration::from_millis(100),
             backoff_multiplier: 1.0,
             max_delay: Duration::from_millis(100),
+            jitter: 0.0,
+            max_retry_time: None,
         };
         retry_with_backoff(config, operation).await
     }
@@ -413,11 +502,26 @@ pub mod pagination {
             }
         }
 
-        /// Update iterator state with response data
+        /// Create a pagination iterator that starts at a given offset
+        pub fn starting_at(page_size: u32, offset: u32) -> Self {
+            Self {
+                current_offset: offset,
+                ..Self::new(page_size)
+            }
+        }
+
+        /// Update iterator state with response data.
+        ///
+        /// When the response does not say whether more items exist, a full page is taken
+        /// to mean there may be another one; an empty page always ends pagination.
         pub fn update_from_response(&mut self, response: &PaginatedResponse<T>) {
+            let received = response.data.len() as u32;
             self.total_items = response.total;
-            self.has_more = response.has_more.unwrap_or(false);
-            self.current_offset += response.data.len() as u32;
+            self.has_more = received > 0
+                && response
+                    .has_more
+                    .unwrap_or(received >= self.page_size);
+            self.current_offset += received;
         }
 
         /// Check if there are more pages available
@@ -515,6 +619,126 @@ pub mod rate_limit {
     }
 }
 
+/// Circuit breaker that fails fast during sustained upstream failures
+pub mod circuit_breaker {
+    use std::time::{Duration, Instant};
+
+    /// Circuit breaker configuration
+    #[derive(Debug, Clone)]
+    pub struct CircuitBreakerConfig {
+        /// Consecutive retryable failures that open the circuit
+        pub failure_threshold: u32,
+        /// How long the circuit stays open before a probe request is let through
+        pub open_duration: Duration,
+    }
+
+    impl Default for CircuitBreakerConfig {
+        fn default() -> Self {
+            Self {
+                failure_threshold: 5,
+                open_duration: Duration::from_secs(30),
+            }
+        }
+    }
+
+    /// State of a circuit breaker
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CircuitState {
+        /// Requests flow normally
+        Closed,
+        /// Requests fail fast until the open period elapses
+        Open,
+        /// One probe request is allowed; its outcome closes or re-opens the circuit
+        HalfOpen,
+    }
+
+    impl std::fmt::Display for CircuitState {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let name = match self {
+                Self::Closed => "closed",
+                Self::Open => "open",
+                Self::HalfOpen => "half-open",
+            };
+            f.write_str(name)
+        }
+    }
+
+    /// Closed / open / half-open circuit breaker
+    #[derive(Debug)]
+    pub struct CircuitBreaker {
+        config: CircuitBreakerConfig,
+        consecutive_failures: u32,
+        opened_at: Option<Instant>,
+        probe_started: Option<Instant>,
+    }
+
+    impl CircuitBreaker {
+        /// Create a closed circuit breaker
+        pub fn new(config: CircuitBreakerConfig) -> Self {
+            Self {
+                config,
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_started: None,
+            }
+        }
+
+        /// Current state
+        pub fn state(&self) -> CircuitState {
+            match self.opened_at {
+                None => CircuitState::Closed,
+                Some(opened) if opened.elapsed() < self.config.open_duration => CircuitState::Open,
+                Some(_) => CircuitState::HalfOpen,
+            }
+        }
+
+        /// Consecutive retryable failures seen since the last success
+        pub fn consecutive_failures(&self) -> u32 {
+            self.consecutive_failures
+        }
+
+        /// Ask to send a request; returns the remaining open time when it must fail fast.
+        ///
+        /// In the half-open state only one probe is let through at a time. A probe that
+        /// never reports back (e.g. a cancelled future) is replaced after `open_duration`.
+        pub fn try_acquire(&mut self) -> std::result::Result<(), Duration> {
+            match self.state() {
+                CircuitState::Closed => Ok(()),
+                CircuitState::Open => {
+                    let opened = self.opened_at.expect("open circuit has an open time");
+                    Err(self.config.open_duration.saturating_sub(opened.elapsed()))
+                }
+                CircuitState::HalfOpen => match self.probe_started {
+                    Some(started) if started.elapsed() < self.config.open_duration => {
+                        Err(self.config.open_duration - started.elapsed())
+                    }
+                    _ => {
+                        self.probe_started = Some(Instant::now());
+                        Ok(())
+                    }
+                },
+            }
+        }
+
+        /// Record a request that reached the API (any non-retryable outcome)
+        pub fn record_success(&mut self) {
+            self.consecutive_failures = 0;
+            self.opened_at = None;
+            self.probe_started = None;
+        }
+
+        /// Record a retryable failure; opens the circuit at the threshold or after a failed probe
+        pub fn record_failure(&mut self) {
+            self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+            let probing = self.state() == CircuitState::HalfOpen;
+            if probing || self.consecutive_failures >= self.config.failure_threshold.max(1) {
+                self.opened_at = Some(Instant::now());
+                self.probe_started = None;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -533,6 +757,13 @@ mod tests {
         assert!(hour_ago < minute_ago);
     }
 
+    #[test]
+    fn test_format_date() {
+        assert_eq!(time::format_date(0), "1970-01-01");
+        assert_eq!(time::format_date(951_782_400), "2000-02-29");
+        assert_eq!(time::format_date(1_706_745_599), "2024-01-31");
+    }
+
     #[test]
     fn test_address_validation() {
         assert!(address::is_valid_ethereum_address(
@@ -584,6 +815,43 @@ mod tests {
         assert_eq!(default_formatted, "0x742d...3b82");
     }
 
+    #[test]
+    fn test_keccak256_known_vectors() {
+        // Ethereum's Keccak-256 (pre-NIST padding), not SHA3-256
+        assert_eq!(
+            address::keccak_hex(""),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            address::keccak_hex("abc"),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+        // Longer than one 136-byte block
+        assert_eq!(
+            address::keccak_hex(&"a".repeat(200)),
+            "96ea54061def936c4be90b518992fdc6f12f535068a256229aca54267b4d084d"
+        );
+    }
+
+    #[test]
+    fn test_fnv1a_known_vectors() {
+        assert_eq!(hash::fnv1a_64(""), 0xcbf29ce484222325);
+        assert_eq!(hash::fnv1a_64("a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(
+            retry::parse_retry_after("120"),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            retry::parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(retry::parse_retry_after("soon"), None);
+    }
+
     #[test]
     fn test_usd_formatting() {
         assert_eq!(finance::format_usd(1_500_000_000.0), "$1.5B");
@@ -637,6 +905,38 @@ mod tests {
         assert!(wait_time > Duration::ZERO);
     }
 
+    #[test]
+    fn test_circuit_breaker_transitions() {
+        use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+
+        let mut breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            open_duration: Duration::from_millis(20),
+        });
+        assert!(breaker.try_acquire().is_ok());
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.try_acquire().is_err());
+
+        std::thread::sleep(Duration::from_millis(25));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.try_acquire().is_ok());
+        // Only one probe at a time
+        assert!(breaker.try_acquire().is_err());
+
+        // A failed probe re-opens the circuit
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(breaker.try_acquire().is_ok());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 0);
+    }
+
     #[test]
     fn test_pagination_iterator() {
         let paginator = pagination::PaginationIterator::<String>::new(10);
@@ -0,0 +1,91 @@
+// file: tests/blocking_tests.rs
+// description: Tests for the synchronous client facade (requires the `blocking` feature)
+// docs_reference: https://docs.rs/tokio/latest/tokio/runtime/
+
+#![cfg(feature = "blocking")]
+
+use krystal_cli::blocking::KrystalApiClient;
+use krystal_cli::client::ClientConfig;
+use krystal_cli::transport::{HttpResponse, MockTransport};
+use krystal_cli::utils::retry::RetryConfig;
+use krystal_cli::{KrystalApiError, PoolsQuery};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn mock_client(transport: Arc<MockTransport>) -> KrystalApiClient {
+    let config = ClientConfig {
+        retry: RetryConfig {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            ..RetryConfig::default()
+        },
+        max_requests_per_second: 1000,
+        ..ClientConfig::default()
+    };
+    KrystalApiClient::with_transport("test-key".to_string(), config, transport).unwrap()
+}
+
+#[test]
+fn test_blocking_get_chains() {
+    let transport = Arc::new(MockTransport::new().with_json(
+        "/v1/chains",
+        json!([{"id": 1, "name": "Ethereum"}, {"id": 8453, "name": "Base"}]),
+    ));
+    let client = mock_client(transport.clone());
+
+    let chains = client.get_chains().unwrap();
+    assert_eq!(chains.len(), 2);
+    assert_eq!(transport.request_count(), 1);
+    assert_eq!(client.usage_report().total_requests(), 1);
+}
+
+#[test]
+fn test_blocking_surfaces_errors() {
+    let transport =
+        Arc::new(MockTransport::new().with_response("/v1/chains", HttpResponse::new(402, "")));
+    let client = mock_client(transport);
+
+    assert!(matches!(client.get_chains(), Err(KrystalApiError::PaymentRequired)));
+}
+
+#[test]
+fn test_blocking_convenience_helpers() {
+    let pool = json!({"poolAddress": "0x1", "poolPrice": 1.0, "feeTier": 500, "tvl": 1.0});
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_json("/v1/pools", json!({"pools": [pool]}))
+            .with_json("/v1/positions", json!({"positions": []})),
+    );
+    let client = mock_client(transport.clone());
+
+    let pools = client.get_top_pools_by_tvl(1, 5).unwrap();
+    assert_eq!(pools.len(), 1);
+
+    let wallet = "0x742d35Cc6639C0532fA20c00fa1A5a6f1a8f3b82";
+    assert!(client.get_open_positions(wallet, Some(1)).unwrap().is_empty());
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[test]
+fn test_blocking_pools_iter_follows_pages() {
+    let pool = |address: &str| {
+        json!({"poolAddress": address, "poolPrice": 1.0, "feeTier": 500, "tvl": 1.0})
+    };
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_json(
+                "/v1/pools",
+                json!({"pools": [pool("0x1"), pool("0x2")], "hasMore": true}),
+            )
+            .with_json("/v1/pools", json!({"pools": [pool("0x3")], "hasMore": false})),
+    );
+    let client = mock_client(transport);
+
+    let pools: Vec<krystal_cli::Pool> = client
+        .pools_iter(PoolsQuery::new().limit(2), None)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let addresses: Vec<_> = pools.iter().map(|p| p.address.as_str()).collect();
+    assert_eq!(addresses, ["0x1", "0x2", "0x3"]);
+}
@@ -5,6 +5,11 @@ use krystal_cli::cli::*;
 use krystal_cli::models::*;
 use std::collections::HashMap;
 
+/// A valid address ending in `suffix`, zero-padded to 40 hex digits
+fn test_address(suffix: &str) -> krystal_cli::Address {
+    format!("0x{suffix:0>40}").parse().unwrap()
+}
+
 #[test]
 fn test_pool_sort_by_conversion() {
     let cli_sort = krystal_cli::cli::app::PoolSortBy::Tvl;
@@ -40,10 +45,10 @@ fn test_output_format_values() {
     // Just ensure they can be created and matched
     for format in formats {
         match format {
-            OutputFormat::Table => assert!(true),
-            OutputFormat::Json => assert!(true),
-            OutputFormat::Csv => assert!(true),
-            OutputFormat::Compact => assert!(true),
+            OutputFormat::Table => {}
+            OutputFormat::Json => {}
+            OutputFormat::Csv => {}
+            OutputFormat::Compact => {}
         }
     }
 }
@@ -80,19 +85,19 @@ fn test_get_token_pair_display() {
     // Test with both tokens present
     let pool_with_tokens = Pool {
         chain: None,
-        address: "0x123".to_string(),
+        address: test_address("123"),
         pool_price: 1.0,
         protocol: None,
         fee_tier: 3000,
         token0: Some(TokenInfo {
-            address: "0x456".to_string(),
+            address: test_address("456"),
             symbol: "USDC".to_string(),
             name: "USD Coin".to_string(),
             decimals: 6,
             logo: None,
         }),
         token1: Some(TokenInfo {
-            address: "0x789".to_string(),
+            address: test_address("789"),
             symbol: "ETH".to_string(),
             name: "Ethereum".to_string(),
             decimals: 18,
@@ -112,7 +117,7 @@ fn test_get_token_pair_display() {
     // Test with missing tokens
     let pool_without_tokens = Pool {
         chain: None,
-        address: "0x123".to_string(),
+        address: test_address("123"),
         pool_price: 1.0,
         protocol: None,
         fee_tier: 3000,
@@ -226,7 +231,7 @@ fn test_cli_parsing_positions_command() {
     let args = vec![
         "krystal-cli",
         "positions",
-        "0x742d35Cc6639C0532fA20c00fa1A5a6f1a8f3b82",
+        "0x742d35cc6639c0532Fa20C00fa1a5a6f1A8f3B82",
         "--chain-id",
         "1",
         "--status",
@@ -249,7 +254,7 @@ fn test_cli_parsing_positions_command() {
                 protocols,
                 ..
             } => {
-                assert_eq!(wallet, "0x742d35Cc6639C0532fA20c00fa1A5a6f1a8f3b82");
+                assert_eq!(wallet, "0x742d35cc6639c0532Fa20C00fa1a5a6f1A8f3B82");
                 assert_eq!(chain_id, Some(1));
                 assert_eq!(status, Some(PositionStatusArg::Open));
                 assert_eq!(protocols, vec!["uniswapv3", "sushiswap"]);
@@ -259,6 +264,28 @@ fn test_cli_parsing_positions_command() {
     }
 }
 
+#[test]
+fn test_cli_parsing_rejects_invalid_addresses() {
+    use clap::Parser;
+
+    let parse = |args: &[&str]| Cli::try_parse_from(args).map(|cli| cli.command);
+
+    assert!(parse(&["krystal-cli", "positions", "0x123"]).is_err());
+    // Mixed case that does not match the EIP-55 checksum is likely a typo
+    assert!(parse(&["krystal-cli", "positions", "0x742d35Cc6639C0532fA20c00fa1A5a6f1a8f3b82"])
+        .is_err());
+    assert!(parse(&["krystal-cli", "pools", "--token", "not-an-address"]).is_err());
+
+    let upper = "0x742D35CC6639C0532FA20C00FA1A5A6F1A8F3B82";
+    match parse(&["krystal-cli", "positions", upper]).unwrap() {
+        Commands::Positions { wallet, .. } => {
+            assert_eq!(wallet.as_str(), upper.to_lowercase());
+            assert_eq!(wallet.to_string(), "0x742d35cc6639c0532Fa20C00fa1a5a6f1A8f3B82");
+        }
+        _ => panic!("Expected Positions command"),
+    }
+}
+
 #[test]
 fn test_cli_parsing_pool_detail_command() {
     use clap::Parser;
@@ -287,8 +314,8 @@ fn test_cli_parsing_pool_detail_command() {
                 assert_eq!(chain_id, 1);
                 assert_eq!(pool_address, "0x7e3d694a81ec15e56a4fea19f3bc841afe462b41");
                 assert_eq!(
-                    factory,
-                    Some("0x1f98431c8ad98523631ae4a59f267346ea31f984".to_string())
+                    factory.unwrap(),
+                    "0x1F98431c8aD98523631AE4a59f267346ea31F984"
                 );
                 assert!(with_incentives);
             }
@@ -366,7 +393,7 @@ fn test_time_parameter_parsing() {
         "krystal-cli",
         "pool-history",
         "1",
-        "0x123",
+        "0x7e3d694a81ec15e56a4fea19f3bc841afe462b41",
         "--start-time",
         "1640995200", // Jan 1, 2022
         "--end-time",
@@ -390,3 +417,671 @@ fn test_time_parameter_parsing() {
         }
     }
 }
+
+#[test]
+fn test_cli_parsing_cassette_flags() {
+    use clap::Parser;
+    use krystal_cli::CassetteMode;
+    use std::path::PathBuf;
+
+    let cli = Cli::try_parse_from(["krystal-cli", "--record", "fixtures", "chains"]).unwrap();
+    assert_eq!(
+        cli.client_config().cassette,
+        Some(CassetteMode::Record(PathBuf::from("fixtures")))
+    );
+
+    let cli = Cli::try_parse_from(["krystal-cli", "--replay", "fixtures", "chains"]).unwrap();
+    assert_eq!(
+        cli.client_config().cassette,
+        Some(CassetteMode::Replay(PathBuf::from("fixtures")))
+    );
+
+    let conflicting = Cli::try_parse_from([
+        "krystal-cli",
+        "--record",
+        "a",
+        "--replay",
+        "b",
+        "chains",
+    ]);
+    assert!(conflicting.is_err());
+}
+
+#[test]
+fn test_cli_parsing_cache_flags() {
+    use clap::Parser;
+    use krystal_cli::EndpointFamily;
+    use krystal_cli::cli::app::CacheAction;
+    use std::time::Duration;
+
+    let cli = Cli::try_parse_from([
+        "krystal-cli",
+        "--refresh",
+        "--cache-ttl",
+        "pools=600",
+        "pools",
+    ])
+    .unwrap();
+    let cache = cli.client_config().cache.unwrap();
+    assert!(cache.refresh);
+    assert_eq!(cache.ttls.pools, Duration::from_secs(600));
+
+    let cli = Cli::try_parse_from(["krystal-cli", "--no-cache", "chains"]).unwrap();
+    assert!(cli.client_config().cache.is_none());
+
+    assert!(Cli::try_parse_from(["krystal-cli", "--cache-ttl", "bogus=1", "chains"]).is_err());
+
+    let cli = Cli::try_parse_from(["krystal-cli", "cache", "clear", "--family", "chains"]).unwrap();
+    match cli.command {
+        Commands::Cache {
+            action: CacheAction::Clear { family },
+        } => assert_eq!(family, Some(EndpointFamily::Chains)),
+        _ => panic!("Expected Cache clear command"),
+    }
+
+    // Every family is accepted and listed in the help text
+    let help = Cli::try_parse_from(["krystal-cli", "cache", "clear", "--help"])
+        .err()
+        .unwrap()
+        .to_string();
+    for family in EndpointFamily::ALL {
+        let name = family.as_str();
+        assert!(help.contains(name), "{name} missing from cache clear help");
+        assert!(Cli::try_parse_from(["krystal-cli", "cache", "clear", "--family", name]).is_ok());
+    }
+}
+
+#[test]
+fn test_cli_parsing_usage_command() {
+    use clap::Parser;
+    use krystal_cli::cli::app::UsagePeriodArg;
+
+    let cli = Cli::try_parse_from([
+        "krystal-cli",
+        "usage",
+        "--period",
+        "monthly",
+        "--days",
+        "30",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Usage {
+            period,
+            days,
+            clear,
+        } => {
+            assert_eq!(period, UsagePeriodArg::Monthly);
+            assert_eq!(days, Some(30));
+            assert!(!clear);
+        }
+        _ => panic!("Expected Usage command"),
+    }
+
+    let cli = Cli::try_parse_from(["krystal-cli", "usage"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Usage {
+            period: UsagePeriodArg::Daily,
+            days: None,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_cli_usage_ledger_path() {
+    use clap::Parser;
+    use krystal_cli::transport::MockTransport;
+    use krystal_cli::KrystalApiClient;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    let name = format!("krystal-cli-ledger-{}.jsonl", std::process::id());
+    let path = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_file(&path);
+    let ledger = path.to_str().unwrap();
+
+    let args = ["krystal-cli", "--no-cache", "--usage-ledger", ledger, "chains"];
+    let cli = Cli::try_parse_from(args).unwrap();
+    assert_eq!(cli.client_config().usage_ledger.as_deref(), Some(path.as_path()));
+
+    let transport = Arc::new(MockTransport::new().with_json("/v1/chains", json!([])));
+    let client =
+        KrystalApiClient::with_transport("test-key".to_string(), cli.client_config(), transport)
+            .unwrap();
+    client.get_chains().await.unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+    std::fs::remove_file(&path).unwrap();
+
+    let cli = Cli::try_parse_from(["krystal-cli", "--no-usage-ledger", "chains"]).unwrap();
+    assert_eq!(cli.client_config().usage_ledger, None);
+
+    let both = ["krystal-cli", "--no-usage-ledger", "--usage-ledger", ledger, "chains"];
+    assert!(Cli::try_parse_from(both).is_err());
+}
+
+#[test]
+fn test_cli_parsing_fallback_keys() {
+    use clap::Parser;
+
+    let cli = Cli::try_parse_from([
+        "krystal-cli",
+        "--api-key",
+        "primary",
+        "--fallback-key",
+        "second",
+        "--fallback-key",
+        "third",
+        "chains",
+    ])
+    .unwrap();
+    assert_eq!(cli.fallback_keys, ["second", "third"]);
+    // Flags override KRYSTAL_FALLBACK_API_KEYS rather than adding to it
+    assert_eq!(cli.client_config().api_keys.len(), 2);
+
+    let cli = Cli::try_parse_from(["krystal-cli", "--fallback-key", "a, b,", "chains"]).unwrap();
+    let keys = cli.client_config().api_keys;
+    assert_eq!(keys, [krystal_cli::ApiKey::new("a"), krystal_cli::ApiKey::new("b")]);
+}
+
+#[test]
+fn test_cli_parsing_pools_pagination_flags() {
+    use clap::Parser;
+
+    let cli = Cli::try_parse_from(["krystal-cli", "pools", "--all", "--limit", "100"]).unwrap();
+    match cli.command {
+        Commands::Pools {
+            all,
+            max_items,
+            limit,
+            ..
+        } => {
+            assert!(all);
+            assert_eq!(max_items, None);
+            assert_eq!(limit, 100);
+        }
+        _ => panic!("Expected Pools command"),
+    }
+
+    let cli = Cli::try_parse_from(["krystal-cli", "pools", "--max-items", "250"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Pools {
+            all: false,
+            max_items: Some(250),
+            page_size: None,
+            ..
+        }
+    ));
+
+    let cli = Cli::try_parse_from(["krystal-cli", "pools", "--all", "--page-size", "200"]).unwrap();
+    assert!(matches!(cli.command, Commands::Pools { page_size: Some(200), .. }));
+    for size in ["0", "1001"] {
+        let args = ["krystal-cli", "pools", "--all", "--page-size", size];
+        assert!(Cli::try_parse_from(args).is_err(), "page size {size} should be rejected");
+    }
+}
+
+#[tokio::test]
+async fn test_pools_all_requests_the_largest_page() {
+    use clap::Parser;
+    use krystal_cli::transport::MockTransport;
+    use krystal_cli::{ClientConfig, KrystalApiClient};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    let transport = Arc::new(MockTransport::new().with_json("/v1/pools", json!({"pools": []})));
+    let client = KrystalApiClient::with_transport(
+        "test-key".to_string(),
+        ClientConfig::default(),
+        transport.clone(),
+    )
+    .unwrap();
+
+    for (args, page_size) in [
+        (vec!["pools", "--all"], "1000"),
+        (vec!["pools", "--max-items", "25"], "25"),
+        (vec!["pools", "--all", "--page-size", "200"], "200"),
+    ] {
+        let cli = Cli::try_parse_from(std::iter::once("krystal-cli").chain(args)).unwrap();
+        execute_command(cli.command, &client, OutputFormat::Json).await.unwrap();
+        let request = transport.requests().pop().unwrap();
+        let limit = request.url.query_pairs().find(|(k, _)| k == "limit").unwrap().1;
+        assert_eq!(limit, page_size);
+    }
+}
+
+#[test]
+fn test_cli_parsing_pool_transactions_all() {
+    use clap::Parser;
+
+    let cli = Cli::try_parse_from([
+        "krystal-cli",
+        "pool-transactions",
+        "1",
+        "0x7e3d694a81ec15e56a4fea19f3bc841afe462b41",
+        "--days-ago",
+        "30",
+        "--all",
+    ])
+    .unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::PoolTransactions {
+            all: true,
+            days_ago: Some(30),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_cli_parsing_range_and_type_filters() {
+    use clap::Parser;
+    use krystal_cli::cli::app::{PositionRangeArg, TransactionKindArg};
+
+    let cli = Cli::try_parse_from([
+        "krystal-cli",
+        "positions",
+        "0x742d35cc6639c0532Fa20C00fa1a5a6f1A8f3B82",
+        "--range",
+        "out",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Positions { range, status, .. } => {
+            assert_eq!(range, Some(PositionRangeArg::Out));
+            assert_eq!(status, None);
+            assert_eq!(
+                PositionRangeStatus::from(range.unwrap()),
+                PositionRangeStatus::OutOfRange
+            );
+        }
+        _ => panic!("Expected Positions command"),
+    }
+
+    let cli = Cli::try_parse_from([
+        "krystal-cli",
+        "pool-transactions",
+        "1",
+        "0x7e3d694a81ec15e56a4fea19f3bc841afe462b41",
+        "--type",
+        "swap",
+        "--type",
+        "increase-liquidity",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::PoolTransactions { kinds, .. } => {
+            assert_eq!(
+                kinds,
+                vec![TransactionKindArg::Swap, TransactionKindArg::IncreaseLiquidity]
+            );
+            assert_eq!(TransactionKind::from(kinds[1]), TransactionKind::IncreaseLiquidity);
+        }
+        _ => panic!("Expected PoolTransactions command"),
+    }
+
+    let bad_range = ["krystal-cli", "positions", "0x0", "--range", "sideways"];
+    assert!(Cli::try_parse_from(bad_range).is_err());
+}
+
+#[test]
+fn test_cli_parsing_transactions_detailed() {
+    use clap::Parser;
+
+    let pool = "0x7e3d694a81ec15e56a4fea19f3bc841afe462b41";
+    let cli = Cli::try_parse_from(["krystal-cli", "pool-transactions", "1", pool, "-d"]).unwrap();
+    assert!(matches!(cli.command, Commands::PoolTransactions { detailed: true, .. }));
+
+    let cli = Cli::try_parse_from([
+        "krystal-cli",
+        "position-transactions",
+        "1",
+        "0xC36442b4a4522E871399CD717aBDD847Ab11FE88",
+        "--token-id",
+        "1",
+        "--detailed",
+    ])
+    .unwrap();
+    assert!(matches!(cli.command, Commands::PositionTransactions { detailed: true, .. }));
+}
+
+#[test]
+fn test_cli_parsing_position_ids() {
+    use clap::Parser;
+    use krystal_cli::cli::app::PositionRef;
+
+    const NFPM: &str = "0xC36442b4a4522E871399CD717aBDD847Ab11FE88";
+    let position_id = format!("{NFPM}-1028436");
+
+    let cli = Cli::try_parse_from(["krystal-cli", "position-detail", "1", &position_id]).unwrap();
+    match cli.command {
+        Commands::PositionDetail { position_id, .. } => {
+            assert_eq!(position_id.nfpm(), NFPM);
+            assert_eq!(position_id.token_id(), "1028436");
+        }
+        _ => panic!("Expected PositionDetail command"),
+    }
+
+    // A position ID supplies both the token address and the token ID
+    let cli =
+        Cli::try_parse_from(["krystal-cli", "position-transactions", "1", &position_id]).unwrap();
+    match cli.command {
+        Commands::PositionTransactions {
+            position, token_id, ..
+        } => {
+            assert!(matches!(position, PositionRef::Id(_)));
+            let (address, token_id) = position.resolve(token_id.as_deref()).unwrap();
+            assert_eq!(address, NFPM);
+            assert_eq!(token_id, Some("1028436"));
+            assert!(position.resolve(Some("7")).is_err());
+        }
+        _ => panic!("Expected PositionTransactions command"),
+    }
+
+    // A bare token address still queries the whole position manager
+    let cli = Cli::try_parse_from(["krystal-cli", "position-transactions", "1", NFPM]).unwrap();
+    match cli.command {
+        Commands::PositionTransactions {
+            position, token_id, ..
+        } => {
+            assert_eq!(position, PositionRef::TokenAddress(NFPM.parse().unwrap()));
+            assert_eq!(position.resolve(token_id.as_deref()).unwrap().1, None);
+        }
+        _ => panic!("Expected PositionTransactions command"),
+    }
+
+    for args in [
+        vec!["position-detail", "1", NFPM],
+        vec!["position-detail", "1", "42"],
+        vec!["position-transactions", "1"],
+        vec!["position-transactions", "1", &position_id, "--token-id", "7"],
+        vec!["position-transactions", "1", "0x123-1"],
+        vec!["position-transactions", "1", "42"],
+    ] {
+        let argv = std::iter::once("krystal-cli").chain(args.iter().copied());
+        assert!(Cli::try_parse_from(argv).is_err(), "{args:?} should not parse");
+    }
+}
+
+#[test]
+fn test_cli_parsing_pools_multi_chain_flags() {
+    use clap::Parser;
+
+    let cli = Cli::try_parse_from(["krystal-cli", "pools", "--chains", "1,8453,56"]).unwrap();
+    match cli.command {
+        Commands::Pools { chains, .. } => assert_eq!(chains, vec![1, 8453, 56]),
+        _ => panic!("Expected Pools command"),
+    }
+
+    let cli = Cli::try_parse_from(["krystal-cli", "pools", "--all-chains"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Pools {
+            all_chains: true,
+            ..
+        }
+    ));
+
+    let conflicting = ["krystal-cli", "pools", "--chains", "1", "--chain-id", "1"];
+    assert!(Cli::try_parse_from(conflicting).is_err());
+    assert!(Cli::try_parse_from(["krystal-cli", "pools", "--all-chains", "--all"]).is_err());
+}
+
+#[test]
+fn test_cli_parsing_network_flags() {
+    use clap::Parser;
+    use std::path::PathBuf;
+
+    let cli = Cli::try_parse_from([
+        "krystal-cli",
+        "--base-url",
+        "http://localhost:8080",
+        "--proxy",
+        "http://proxy.internal:3128",
+        "--timeout",
+        "5",
+        "--retries",
+        "0",
+        "--rps",
+        "2",
+        "--ca-cert",
+        "corp-root.pem",
+        "chains",
+    ])
+    .unwrap();
+    let config = cli.client_config();
+    assert_eq!(config.base_url, "http://localhost:8080");
+    assert_eq!(config.proxy.as_deref(), Some("http://proxy.internal:3128"));
+    assert_eq!(config.timeout_secs, 5);
+    assert_eq!(config.retry.max_attempts, 1);
+    assert_eq!(config.max_requests_per_second, 2);
+    assert_eq!(config.ca_cert, Some(PathBuf::from("corp-root.pem")));
+    assert!(!config.allow_insecure);
+
+    let cli = Cli::try_parse_from(["krystal-cli", "--allow-insecure", "chains"]).unwrap();
+    assert!(cli.client_config().allow_insecure);
+
+    assert!(Cli::try_parse_from(["krystal-cli", "--timeout", "soon", "chains"]).is_err());
+    assert!(Cli::try_parse_from(["krystal-cli", "--timeout", "0", "chains"]).is_err());
+
+    let max = u32::MAX.to_string();
+    let cli = Cli::try_parse_from(["krystal-cli", "--retries", &max, "chains"]).unwrap();
+    assert_eq!(cli.client_config().retry.max_attempts, u32::MAX);
+}
+
+#[test]
+fn test_cli_parsing_schema_check() {
+    use clap::Parser;
+    use krystal_cli::ParseMode;
+
+    let cli = Cli::try_parse_from([
+        "krystal-cli",
+        "--strict",
+        "schema-check",
+        "--chain-id",
+        "8453",
+        "--wallet",
+        "0x742d35cc6639c0532Fa20C00fa1a5a6f1A8f3B82",
+        "--fail-on-unknown",
+    ])
+    .unwrap();
+    match &cli.command {
+        Commands::SchemaCheck {
+            chain_id,
+            limit,
+            wallet,
+            fail_on_unknown,
+        } => {
+            assert_eq!(*chain_id, 8453);
+            assert_eq!(*limit, 20);
+            assert!(wallet.is_some());
+            assert!(*fail_on_unknown);
+        }
+        _ => panic!("expected schema-check"),
+    }
+    // schema-check parses leniently so it can report drift instead of failing on it
+    assert_eq!(cli.client_config().parse_mode, ParseMode::Lenient);
+
+    let cli = Cli::try_parse_from(["krystal-cli", "--strict", "chains"]).unwrap();
+    assert_eq!(cli.client_config().parse_mode, ParseMode::Strict);
+}
+
+#[test]
+fn test_cli_parsing_raw() {
+    use clap::Parser;
+
+    let cli = Cli::try_parse_from([
+        "krystal-cli",
+        "raw",
+        "v1/pools/1/0xabc/fees",
+        "-q",
+        "protocols=uniswapv3",
+        "--query",
+        "protocols=sushiswap",
+        "-q",
+        "note=a=b",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Raw { path, query } => {
+            assert_eq!(path, "v1/pools/1/0xabc/fees");
+            let pairs: Vec<(&str, &str)> =
+                query.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+            assert_eq!(
+                pairs,
+                [("protocols", "uniswapv3"), ("protocols", "sushiswap"), ("note", "a=b")]
+            );
+        }
+        _ => panic!("expected raw"),
+    }
+
+    assert!(Cli::try_parse_from(["krystal-cli", "raw", "v1/chains", "-q", "novalue"]).is_err());
+    assert!(Cli::try_parse_from(["krystal-cli", "raw", "v1/chains", "-q", "=1"]).is_err());
+}
+
+#[test]
+fn test_cli_parsing_spec_check() {
+    use clap::Parser;
+    use krystal_cli::SpecCheck;
+
+    let cli = Cli::try_parse_from(["krystal-cli", "chains"]).unwrap();
+    assert_eq!(cli.client_config().spec_check, SpecCheck::Off);
+
+    let cli = Cli::try_parse_from(["krystal-cli", "--spec-check", "strict", "chains"]).unwrap();
+    assert_eq!(cli.client_config().spec_check, SpecCheck::Strict);
+
+    assert!(Cli::try_parse_from(["krystal-cli", "--spec-check", "loud", "chains"]).is_err());
+}
+
+#[tokio::test]
+async fn test_schema_check_ignores_cached_responses() {
+    use krystal_cli::transport::MockTransport;
+    use krystal_cli::{CacheConfig, ClientConfig, KrystalApiClient, KrystalApiError};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    let dir = std::env::temp_dir().join(format!("krystal-schema-cache-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    // The API has since added `gasToken`, but the cache still holds the old body
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}]))
+            .with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum", "gasToken": "ETH"}]))
+            .with_json("/v1/protocols", json!([]))
+            .with_json("/v1/chains/1", json!({"id": 1, "name": "Ethereum"}))
+            .with_json("/v1/pools", json!({"pools": []})),
+    );
+    let config = ClientConfig {
+        cache: Some(CacheConfig::new(&dir)),
+        ..ClientConfig::default()
+    };
+    let client = KrystalApiClient::with_transport("test-key".to_string(), config, transport)
+        .unwrap();
+    client.get_chains().await.unwrap();
+
+    let command = Commands::SchemaCheck {
+        chain_id: 1,
+        limit: 5,
+        wallet: None,
+        fail_on_unknown: true,
+    };
+    let err = execute_command(command, &client, OutputFormat::Json)
+        .await
+        .unwrap_err();
+    match err {
+        KrystalApiError::SchemaDrift { fields, .. } => assert_eq!(fields, ["ChainInfo.gasToken"]),
+        other => panic!("expected schema drift, got {other:?}"),
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_cache_command_runs_through_execute_command() {
+    use krystal_cli::cli::app::CacheAction;
+    use krystal_cli::transport::MockTransport;
+    use krystal_cli::{CacheConfig, ClientConfig, KrystalApiClient};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    let dir = std::env::temp_dir().join(format!("krystal-cache-cmd-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let transport = Arc::new(MockTransport::new().with_json("/v1/chains", json!([])));
+    let config = ClientConfig {
+        cache: Some(CacheConfig::new(&dir)),
+        ..ClientConfig::default()
+    };
+    let client = KrystalApiClient::with_transport("test-key".to_string(), config, transport)
+        .unwrap();
+    client.get_chains().await.unwrap();
+    assert_eq!(
+        krystal_cli::DiskCache::new(CacheConfig::new(&dir)).stats().unwrap().total_entries,
+        1
+    );
+
+    let clear = Commands::Cache {
+        action: CacheAction::Clear { family: None },
+    };
+    execute_command(clear, &client, OutputFormat::Json).await.unwrap();
+    assert_eq!(
+        krystal_cli::DiskCache::new(CacheConfig::new(&dir)).stats().unwrap().total_entries,
+        0
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_pool_transactions_type_filter_pages_until_limit() {
+    use clap::Parser;
+    use krystal_cli::transport::MockTransport;
+    use krystal_cli::{ClientConfig, KrystalApiClient};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    const POOL: &str = "0x7e3d694a81ec15e56a4fea19f3bc841afe462b41";
+    let tx = |hash: &str, kind: &str| {
+        json!({"hash": hash, "timestamp": 1, "type": kind, "amount0": 1, "amount1": 1})
+    };
+    let path = format!("/v1/pools/1/{POOL}/transactions");
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_json(&path, json!({"transactions": [tx("a", "swap"), tx("b", "mint")]}))
+            .with_json(&path, json!({"transactions": [tx("c", "burn"), tx("d", "swap")]}))
+            .with_json(&path, json!({"transactions": [tx("e", "swap")]})),
+    );
+    let client = KrystalApiClient::with_transport(
+        "test-key".to_string(),
+        ClientConfig::default(),
+        transport.clone(),
+    )
+    .unwrap();
+
+    let cli = Cli::try_parse_from([
+        "krystal-cli",
+        "pool-transactions",
+        "1",
+        POOL,
+        "--limit",
+        "2",
+        "--type",
+        "swap",
+    ])
+    .unwrap();
+    execute_command(cli.command, &client, OutputFormat::Json)
+        .await
+        .unwrap();
+
+    // Two matches need two pages; the third page is never requested
+    let offsets: Vec<String> = transport
+        .requests()
+        .iter()
+        .map(|r| r.url.query_pairs().find(|(k, _)| k == "offset").unwrap().1.into_owned())
+        .collect();
+    assert_eq!(offsets, ["0", "2"]);
+}
@@ -1,9 +1,914 @@
 //! Tests for the API client functionality
 
-use krystal_cli::client::KrystalApiClient;
+use krystal_cli::client::{ClientConfig, KrystalApiClient};
+use krystal_cli::transport::{HttpResponse, MockTransport};
+use krystal_cli::utils::retry::RetryConfig;
+use krystal_cli::{KrystalApiError, PoolsQuery};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn mock_client(transport: Arc<MockTransport>) -> KrystalApiClient {
+    let config = ClientConfig {
+        retry: RetryConfig {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            backoff_multiplier: 1.0,
+            max_delay: Duration::from_millis(1),
+            ..RetryConfig::default()
+        },
+        max_requests_per_second: 1000,
+        ..ClientConfig::default()
+    };
+    KrystalApiClient::with_transport("test-key".to_string(), config, transport).unwrap()
+}
 
 #[test]
 fn test_client_creation() {
     let client = KrystalApiClient::new("test-key".to_string());
     assert!(client.is_ok());
 }
+
+#[test]
+fn test_plain_http_base_url_requires_loopback_or_opt_in() {
+    let client_with = |base_url: &str, allow_insecure: bool| {
+        let config = ClientConfig {
+            base_url: base_url.to_string(),
+            allow_insecure,
+            ..ClientConfig::default()
+        };
+        KrystalApiClient::with_config("test-key".to_string(), config)
+    };
+
+    assert!(client_with("http://localhost:8080", false).is_ok());
+    assert!(client_with("http://127.0.0.1:8080", false).is_ok());
+    assert!(client_with("http://[::1]:8080", false).is_ok());
+    assert!(matches!(
+        client_with("http://staging.example.com", false),
+        Err(KrystalApiError::InvalidParams(_))
+    ));
+    assert!(client_with("http://staging.example.com", true).is_ok());
+    assert!(client_with("ftp://staging.example.com", true).is_err());
+}
+
+#[tokio::test]
+async fn test_get_chains_through_mock_transport() {
+    let transport = Arc::new(MockTransport::new().with_json(
+        "/v1/chains",
+        json!([{"id": 1, "name": "Ethereum"}, {"id": 8453, "name": "Base"}]),
+    ));
+    let client = mock_client(transport.clone());
+
+    let chains = client.get_chains().await.unwrap();
+    assert_eq!(chains.len(), 2);
+    assert_eq!(chains[1].name, "Base");
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].headers.get("KC-APIKey").unwrap(), "test-key");
+}
+
+#[tokio::test]
+async fn test_get_pools_sends_query_params() {
+    let transport = Arc::new(MockTransport::new().with_json("/v1/pools", json!({"pools": []})));
+    let client = mock_client(transport.clone());
+
+    let pools = client
+        .get_pools(PoolsQuery::new().chain_id(56).limit(5))
+        .await
+        .unwrap();
+    assert!(pools.is_empty());
+
+    let query = transport.requests()[0]
+        .url
+        .query()
+        .unwrap_or("")
+        .to_string();
+    assert!(query.contains("chainId=56"));
+    assert!(query.contains("limit=5"));
+}
+
+#[tokio::test]
+async fn test_retries_server_errors_then_succeeds() {
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_response("/v1/protocols", HttpResponse::new(503, "unavailable"))
+            .with_json(
+                "/v1/protocols",
+                json!([{"key": "uniswapv3", "name": "Uniswap V3"}]),
+            ),
+    );
+    let client = mock_client(transport.clone());
+
+    let protocols = client.get_protocols().await.unwrap();
+    assert_eq!(protocols.len(), 1);
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[tokio::test]
+async fn test_maps_payment_required() {
+    let transport =
+        Arc::new(MockTransport::new().with_response("/v1/chains", HttpResponse::new(402, "")));
+    let client = mock_client(transport.clone());
+
+    let err = client.get_chains().await.unwrap_err();
+    assert!(matches!(err, KrystalApiError::PaymentRequired));
+    assert_eq!(transport.request_count(), 1);
+}
+
+#[tokio::test]
+async fn test_disk_cache_serves_repeat_requests() {
+    use krystal_cli::CacheConfig;
+
+    let dir = std::env::temp_dir().join(format!("krystal-client-cache-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let transport = Arc::new(
+        MockTransport::new().with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}])),
+    );
+    let config = ClientConfig {
+        cache: Some(CacheConfig::new(&dir)),
+        ..ClientConfig::default()
+    };
+    let client =
+        KrystalApiClient::with_transport("test-key".to_string(), config, transport.clone())
+            .unwrap();
+
+    client.get_chains().await.unwrap();
+    let chains = client.get_chains().await.unwrap();
+    assert_eq!(chains[0].name, "Ethereum");
+    assert_eq!(transport.request_count(), 1);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_cassettes_bypass_the_disk_cache() {
+    use krystal_cli::{CacheConfig, CassetteMode};
+
+    let root = std::env::temp_dir().join(format!("krystal-cassette-cache-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    let (cache_dir, cassette_dir) = (root.join("cache"), root.join("cassette"));
+    let client_with = |cassette: Option<CassetteMode>, transport: Arc<MockTransport>| {
+        let config = ClientConfig {
+            cache: Some(CacheConfig::new(&cache_dir)),
+            cassette,
+            ..ClientConfig::default()
+        };
+        KrystalApiClient::with_transport("test-key".to_string(), config, transport).unwrap()
+    };
+
+    // Warm the cache with a live response
+    let live = Arc::new(
+        MockTransport::new().with_json("/v1/chains", json!([{"id": 1, "name": "Cached"}])),
+    );
+    client_with(None, live).get_chains().await.unwrap();
+
+    // Recording still reaches the network and the cassette despite the warm cache
+    let recorded = Arc::new(
+        MockTransport::new().with_json("/v1/chains", json!([{"id": 1, "name": "Recorded"}])),
+    );
+    let record = Some(CassetteMode::Record(cassette_dir.clone()));
+    let chains = client_with(record, recorded.clone()).get_chains().await.unwrap();
+    assert_eq!(chains[0].name, "Recorded");
+    assert_eq!(recorded.request_count(), 1);
+    assert_eq!(std::fs::read_dir(&cassette_dir).unwrap().count(), 1);
+
+    // Replay serves the cassette, not the cache
+    let replay = Some(CassetteMode::Replay(cassette_dir.clone()));
+    let chains = client_with(replay, Arc::new(MockTransport::new()))
+        .get_chains()
+        .await
+        .unwrap();
+    assert_eq!(chains[0].name, "Recorded");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn test_concurrent_identical_requests_are_coalesced() {
+    let pool = json!({"poolAddress": "0xpool", "poolPrice": 1.5, "feeTier": 500, "tvl": 1000.0});
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_latency(Duration::from_millis(50))
+            .with_json("/v1/pools/1/0xpool", pool),
+    );
+    let client = Arc::new(mock_client(transport.clone()));
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.get_pool_detail(1, "0xpool", None, false).await })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.await.unwrap().unwrap().address, "0xpool");
+    }
+    assert_eq!(transport.request_count(), 1);
+
+    client
+        .get_pool_detail(1, "0xpool", None, false)
+        .await
+        .unwrap();
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[tokio::test]
+async fn test_coalesced_callers_share_errors() {
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_latency(Duration::from_millis(50))
+            .with_response("/v1/chains", HttpResponse::new(402, "")),
+    );
+    let client = Arc::new(mock_client(transport.clone()));
+
+    let (a, b) = tokio::join!(client.get_chains(), client.get_chains());
+    assert!(matches!(a, Err(KrystalApiError::PaymentRequired)));
+    assert!(matches!(b, Err(KrystalApiError::PaymentRequired)));
+    assert_eq!(transport.request_count(), 1);
+}
+
+#[tokio::test]
+async fn test_memory_cache_memoizes_responses() {
+    use krystal_cli::MemoryCacheConfig;
+
+    let transport = Arc::new(
+        MockTransport::new().with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}])),
+    );
+    let config = ClientConfig {
+        memory_cache: Some(MemoryCacheConfig::default()),
+        ..ClientConfig::default()
+    };
+    let client =
+        KrystalApiClient::with_transport("test-key".to_string(), config, transport.clone())
+            .unwrap();
+
+    client.get_chains().await.unwrap();
+    client.get_chains().await.unwrap();
+    assert_eq!(transport.request_count(), 1);
+
+    client.clear_memory_cache();
+    client.get_chains().await.unwrap();
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[tokio::test]
+async fn test_refresh_skips_memory_cache_reads() {
+    use krystal_cli::{CacheConfig, MemoryCacheConfig};
+
+    let dir = std::env::temp_dir().join(format!("krystal-refresh-memo-{}", std::process::id()));
+    let transport = Arc::new(
+        MockTransport::new().with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}])),
+    );
+    let config = ClientConfig {
+        cache: Some(CacheConfig {
+            refresh: true,
+            ..CacheConfig::new(&dir)
+        }),
+        memory_cache: Some(MemoryCacheConfig::default()),
+        ..ClientConfig::default()
+    };
+    let client =
+        KrystalApiClient::with_transport("test-key".to_string(), config, transport.clone())
+            .unwrap();
+
+    client.get_chains().await.unwrap();
+    client.get_chains().await.unwrap();
+    assert_eq!(transport.request_count(), 2);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_rate_limited_requests_are_retried() {
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_response(
+                "/v1/chains",
+                HttpResponse::new(429, "slow down").with_header("Retry-After", "0"),
+            )
+            .with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}])),
+    );
+    let client = mock_client(transport.clone());
+
+    assert_eq!(client.get_chains().await.unwrap().len(), 1);
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[tokio::test]
+async fn test_maps_rate_limited_with_retry_after() {
+    let transport = Arc::new(MockTransport::new().with_response(
+        "/v1/chains",
+        HttpResponse::new(429, "").with_header("Retry-After", "120"),
+    ));
+    let client = mock_client(transport);
+
+    let err = client.get_chains().await.unwrap_err();
+    assert!(matches!(
+        err,
+        KrystalApiError::RateLimited {
+            retry_after: Some(wait)
+        } if wait == Duration::from_secs(120)
+    ));
+}
+
+#[tokio::test]
+async fn test_usage_report_and_ledger() {
+    use krystal_cli::UsageLedger;
+
+    let path =
+        std::env::temp_dir().join(format!("krystal-client-usage-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_response("/v1/chains", HttpResponse::new(503, "busy"))
+            .with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}]))
+            .with_json(
+                "/v1/pools/1/0xpool",
+                json!({"poolAddress": "0xpool", "poolPrice": 1.0, "feeTier": 500, "tvl": 1.0}),
+            ),
+    );
+    let config = ClientConfig {
+        usage_ledger: Some(path.clone()),
+        retry: RetryConfig {
+            base_delay: Duration::from_millis(1),
+            jitter: 0.0,
+            ..RetryConfig::default()
+        },
+        ..ClientConfig::default()
+    };
+    let client =
+        KrystalApiClient::with_transport("test-key".to_string(), config, transport).unwrap();
+
+    client.get_chains().await.unwrap();
+    client
+        .get_pool_detail(1, "0xpool", None, false)
+        .await
+        .unwrap();
+
+    let report = client.usage_report();
+    assert_eq!(report.total_requests(), 3);
+    let chains = &report.endpoints["/v1/chains"];
+    assert_eq!(chains.requests, 2);
+    assert_eq!(chains.errors, 1);
+    assert_eq!(chains.status_codes[&503], 1);
+    assert_eq!(report.endpoints["/v1/pools/{id}/{address}"].requests, 1);
+
+    let records = UsageLedger::new(&path).records().unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[2].endpoint, "/v1/pools/{id}/{address}");
+
+    client.reset_usage();
+    assert_eq!(client.usage_report().total_requests(), 0);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_fails_over_to_next_key_on_payment_required() {
+    use krystal_cli::ApiKey;
+
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_response("/v1/chains", HttpResponse::new(402, ""))
+            .with_response("/v1/chains", HttpResponse::new(401, ""))
+            .with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}])),
+    );
+    let config = ClientConfig {
+        api_keys: vec![ApiKey::new("key-b"), ApiKey::new("key-c")],
+        ..ClientConfig::default()
+    };
+    let client =
+        KrystalApiClient::with_transport("key-a".to_string(), config, transport.clone()).unwrap();
+
+    client.get_chains().await.unwrap();
+    client.get_protocols().await.ok();
+
+    let keys: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|request| request.headers["KC-APIKey"].to_str().unwrap().to_string())
+        .collect();
+    // Rotation is sticky: later requests keep using the key that worked
+    assert_eq!(keys, ["key-a", "key-b", "key-c", "key-c"]);
+    assert_eq!(
+        client.active_key_fingerprint(),
+        ApiKey::new("key-c").fingerprint()
+    );
+
+    let report = client.usage_report();
+    assert_eq!(report.keys[&ApiKey::new("key-a").fingerprint()], 1);
+    assert_eq!(report.keys[&ApiKey::new("key-c").fingerprint()], 2);
+
+    client.reset_key_rotation();
+    assert_eq!(
+        client.active_key_fingerprint(),
+        ApiKey::new("key-a").fingerprint()
+    );
+}
+
+#[tokio::test]
+async fn test_returns_error_when_all_keys_rejected() {
+    use krystal_cli::ApiKey;
+
+    let transport =
+        Arc::new(MockTransport::new().with_response("/v1/chains", HttpResponse::new(402, "")));
+    let config = ClientConfig {
+        api_keys: vec![ApiKey::new("key-b")],
+        ..ClientConfig::default()
+    };
+    let client =
+        KrystalApiClient::with_transport("key-a".to_string(), config, transport.clone()).unwrap();
+
+    let err = client.get_chains().await.unwrap_err();
+    assert!(matches!(err, KrystalApiError::PaymentRequired));
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[tokio::test]
+async fn test_pools_stream_follows_pages_until_exhausted() {
+    use futures::TryStreamExt;
+
+    let pool = |address: &str| {
+        json!({"poolAddress": address, "poolPrice": 1.0, "feeTier": 500, "tvl": 1.0})
+    };
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_json(
+                "/v1/pools",
+                json!({"pools": [pool("0x1"), pool("0x2")], "hasMore": true}),
+            )
+            .with_json(
+                "/v1/pools",
+                json!({"pools": [pool("0x3")], "hasMore": false}),
+            ),
+    );
+    let client = mock_client(transport.clone());
+
+    let pools: Vec<_> = client
+        .pools_stream(PoolsQuery::new().chain_id(1).limit(2), None)
+        .try_collect()
+        .await
+        .unwrap();
+    let addresses: Vec<_> = pools.iter().map(|p| p.address.as_str()).collect();
+    assert_eq!(addresses, ["0x1", "0x2", "0x3"]);
+
+    let queries: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|r| r.url.query().unwrap_or("").to_string())
+        .collect();
+    assert_eq!(queries.len(), 2);
+    assert!(queries[0].contains("limit=2") && queries[0].contains("offset=0"));
+    assert!(queries[1].contains("chainId=1") && queries[1].contains("offset=2"));
+}
+
+#[tokio::test]
+async fn test_pools_stream_stops_at_max_items() {
+    use futures::TryStreamExt;
+
+    let pool = |address: &str| {
+        json!({"poolAddress": address, "poolPrice": 1.0, "feeTier": 500, "tvl": 1.0})
+    };
+    // No pagination metadata: full pages imply there may be more
+    let transport = Arc::new(MockTransport::new().with_json(
+        "/v1/pools",
+        json!({"pools": [pool("0x1"), pool("0x2")]}),
+    ));
+    let client = mock_client(transport.clone());
+
+    let pools: Vec<_> = client
+        .pools_stream(PoolsQuery::new().limit(2), Some(3))
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(pools.len(), 3);
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[tokio::test]
+async fn test_pools_stream_yields_error_and_ends() {
+    use futures::StreamExt;
+
+    let transport =
+        Arc::new(MockTransport::new().with_response("/v1/pools", HttpResponse::new(402, "")));
+    let client = mock_client(transport);
+
+    let results: Vec<_> = client.pools_stream(PoolsQuery::new(), None).collect().await;
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results[0], Err(KrystalApiError::PaymentRequired)));
+}
+
+#[tokio::test]
+async fn test_get_pool_details_returns_per_item_results_in_order() {
+    let pool = |address: &str| {
+        json!({"poolAddress": address, "poolPrice": 1.0, "feeTier": 500, "tvl": 1.0})
+    };
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_json("/v1/pools/1/0xaaa", pool("0xaaa"))
+            .with_response("/v1/pools/1/0xbad", HttpResponse::new(400, "invalid address"))
+            .with_json("/v1/pools/8453/0xccc", pool("0xccc")),
+    );
+    let client = mock_client(transport.clone());
+
+    let results = client
+        .get_pool_details(&[(1, "0xaaa"), (1, "0xbad"), (8453, "0xccc")], false, 2)
+        .await;
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().address, "0xaaa");
+    assert!(matches!(results[1], Err(KrystalApiError::InvalidParams(_))));
+    assert_eq!(results[2].as_ref().unwrap().address, "0xccc");
+    assert_eq!(transport.request_count(), 3);
+}
+
+#[tokio::test]
+async fn test_batch_requests_respect_concurrency_cap() {
+    let pool = json!({"poolAddress": "0xpool", "poolPrice": 1.0, "feeTier": 500, "tvl": 1.0});
+    let addresses = ["0x1", "0x2", "0x3", "0x4", "0x5", "0x6"];
+    let mut transport = MockTransport::new().with_latency(Duration::from_millis(100));
+    for address in addresses {
+        transport = transport.with_json(&format!("/v1/pools/1/{address}"), pool.clone());
+    }
+    let client = mock_client(Arc::new(transport));
+    let pools: Vec<_> = addresses.iter().map(|a| (1, *a)).collect();
+
+    let started = std::time::Instant::now();
+    let results = client.get_pool_details(&pools, false, 3).await;
+    let elapsed = started.elapsed();
+
+    assert!(results.iter().all(|r| r.is_ok()));
+    // Two rounds of three requests: slower than fully parallel, faster than sequential
+    assert!(elapsed >= Duration::from_millis(200), "{elapsed:?}");
+    assert!(elapsed < Duration::from_millis(500), "{elapsed:?}");
+}
+
+#[tokio::test]
+async fn test_get_positions_many_isolates_invalid_wallets() {
+    let transport = Arc::new(MockTransport::new().with_json("/v1/positions", json!([])));
+    let client = mock_client(transport.clone());
+
+    let results = client
+        .get_positions_many(
+            &["0x742d35Cc6639C0532fA20c00fa1A5a6f1a8f3b82", "not-a-wallet"],
+            Some(1),
+            0,
+        )
+        .await;
+    assert!(results[0].as_ref().unwrap().is_empty());
+    assert!(matches!(results[1], Err(KrystalApiError::InvalidParams(_))));
+    assert_eq!(transport.request_count(), 1);
+    assert!(transport.requests()[0].url.query().unwrap().contains("chainId=1"));
+}
+
+#[tokio::test]
+async fn test_multi_chain_pools_are_merged_sorted_and_limited() {
+    use krystal_cli::PoolSortBy;
+
+    let pool = |address: &str, tvl: f64| {
+        json!({"poolAddress": address, "poolPrice": 1.0, "feeTier": 500, "tvl": tvl})
+    };
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_json(
+                "/v1/pools",
+                json!({"pools": [pool("0xa1", 900.0), pool("0xa2", 100.0), pool("0xa3", 50.0)]}),
+            )
+            .with_json(
+                "/v1/pools",
+                json!({"pools": [pool("0xb1", 500.0), pool("0xb2", 400.0), pool("0xb3", 10.0)]}),
+            ),
+    );
+    let client = mock_client(transport.clone());
+
+    let pools = client
+        .get_pools(
+            PoolsQuery::new()
+                .chain_ids([1, 8453])
+                .sort_by(PoolSortBy::Tvl)
+                .limit(2)
+                .offset(1),
+        )
+        .await
+        .unwrap();
+    let addresses: Vec<_> = pools.iter().map(|p| p.address.as_str()).collect();
+    assert_eq!(addresses, ["0xb1", "0xb2"]);
+
+    let queries: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|r| r.url.query().unwrap_or("").to_string())
+        .collect();
+    assert_eq!(queries.len(), 2);
+    assert!(queries.iter().any(|q| q.contains("chainId=1&")));
+    assert!(queries.iter().any(|q| q.contains("chainId=8453&")));
+    // Each chain is asked for offset + limit pools and no offset
+    assert!(queries.iter().all(|q| q.contains("limit=3") && !q.contains("offset")));
+}
+
+#[tokio::test]
+async fn test_multi_chain_pools_skip_failed_chains() {
+    let pool = json!({"poolAddress": "0xpool", "poolPrice": 1.0, "feeTier": 500, "tvl": 1.0});
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_response("/v1/pools", HttpResponse::new(400, "unsupported chain"))
+            .with_json("/v1/pools", json!({"pools": [pool]})),
+    );
+    let client = mock_client(transport.clone());
+
+    let pools = client
+        .get_pools(PoolsQuery::new().chain_ids([1, 56]))
+        .await
+        .unwrap();
+    assert_eq!(pools.len(), 1);
+    assert_eq!(pools[0].address, "0xpool");
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[tokio::test]
+async fn test_multi_chain_pools_fail_when_every_chain_fails() {
+    let transport =
+        Arc::new(MockTransport::new().with_response("/v1/pools", HttpResponse::new(400, "bad")));
+    let client = mock_client(transport);
+
+    let err = client
+        .get_pools(PoolsQuery::new().chain_ids([1, 56]))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, KrystalApiError::InvalidParams(_)));
+}
+
+#[tokio::test]
+async fn test_middleware_sees_every_attempt_in_order() {
+    use krystal_cli::transport::HttpRequest;
+    use krystal_cli::{Middleware, MiddlewareStack, ResponseInfo};
+    use std::sync::Mutex;
+
+    struct TraceHeader;
+
+    impl Middleware for TraceHeader {
+        fn before_request(&self, request: &mut HttpRequest) {
+            request.headers.insert("x-trace-id", "abc".parse().unwrap());
+        }
+    }
+
+    struct Audit(&'static str, Arc<Mutex<Vec<String>>>);
+
+    impl Middleware for Audit {
+        fn before_request(&self, request: &mut HttpRequest) {
+            let seen_trace = request.headers.contains_key("x-trace-id");
+            self.1.lock().unwrap().push(format!("{} before trace={}", self.0, seen_trace));
+        }
+
+        fn after_response(&self, response: &ResponseInfo<'_>) {
+            let outcome = if response.result.is_ok() { "ok" } else { "err" };
+            let status = response.status.unwrap_or(0);
+            self.1.lock().unwrap().push(format!("{} {} {}", self.0, status, outcome));
+        }
+    }
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_response("/v1/chains", HttpResponse::new(503, "busy"))
+            .with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}])),
+    );
+    let config = ClientConfig {
+        retry: RetryConfig {
+            base_delay: Duration::from_millis(1),
+            jitter: 0.0,
+            ..RetryConfig::default()
+        },
+        middleware: MiddlewareStack::new()
+            .with(Audit("first", log.clone()))
+            .with(TraceHeader)
+            .with(Audit("second", log.clone())),
+        ..ClientConfig::default()
+    };
+    let client =
+        KrystalApiClient::with_transport("test-key".to_string(), config, transport.clone())
+            .unwrap();
+
+    client.get_chains().await.unwrap();
+
+    assert!(transport.requests().iter().all(|r| r.headers["x-trace-id"] == "abc"));
+    let expected = [
+        "first before trace=false",
+        "second before trace=true",
+        "first 503 err",
+        "second 503 err",
+        "first before trace=false",
+        "second before trace=true",
+        "first 200 ok",
+        "second 200 ok",
+    ];
+    assert_eq!(*log.lock().unwrap(), expected);
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_fails_fast_then_recovers() {
+    use krystal_cli::{CircuitBreakerConfig, CircuitState};
+
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_response("/v1/chains", HttpResponse::new(503, "down"))
+            .with_response("/v1/chains", HttpResponse::new(503, "down"))
+            .with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}])),
+    );
+    let config = ClientConfig {
+        retry: RetryConfig {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(1),
+            jitter: 0.0,
+            ..RetryConfig::default()
+        },
+        circuit_breaker: Some(CircuitBreakerConfig {
+            failure_threshold: 2,
+            open_duration: Duration::from_millis(50),
+        }),
+        ..ClientConfig::default()
+    };
+    let client =
+        KrystalApiClient::with_transport("test-key".to_string(), config, transport.clone())
+            .unwrap();
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+
+    // The breaker opens after two 503s and cuts the retry schedule short
+    let err = client.get_chains().await.unwrap_err();
+    assert!(matches!(err, KrystalApiError::CircuitOpen { .. }));
+    assert_eq!(transport.request_count(), 2);
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+
+    // Fails fast without touching the network while open
+    assert!(matches!(
+        client.get_chains().await,
+        Err(KrystalApiError::CircuitOpen { .. })
+    ));
+    assert_eq!(transport.request_count(), 2);
+
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    assert_eq!(client.circuit_state(), Some(CircuitState::HalfOpen));
+    assert_eq!(client.get_chains().await.unwrap().len(), 1);
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+}
+
+#[tokio::test]
+async fn test_strict_parse_mode_rejects_unknown_fields() {
+    use krystal_cli::ParseMode;
+
+    let transport = Arc::new(MockTransport::new().with_json(
+        "/v1/chains",
+        json!([{"id": 1, "name": "Ethereum", "rpcUrl": "https://rpc"}]),
+    ));
+
+    let lenient = mock_client(transport.clone());
+    let chains = lenient.get_chains().await.unwrap();
+    assert!(chains[0].additional_fields.contains_key("rpcUrl"));
+
+    let config = ClientConfig {
+        parse_mode: ParseMode::Strict,
+        ..ClientConfig::default()
+    };
+    let strict =
+        KrystalApiClient::with_transport("test-key".to_string(), config, transport).unwrap();
+    match strict.get_chains().await {
+        Err(KrystalApiError::SchemaDrift { context, fields }) => {
+            assert_eq!(context, "chains");
+            assert_eq!(fields, ["ChainInfo.rpcUrl"]);
+        }
+        other => panic!("expected SchemaDrift, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_request_options_cancel_and_deadline() {
+    use krystal_cli::{CancellationToken, RequestOptions};
+
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_latency(Duration::from_millis(200))
+            .with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}])),
+    );
+    let client = mock_client(transport);
+
+    let token = CancellationToken::new();
+    let options = RequestOptions::new().cancel_token(token.clone());
+    let canceller = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        token.cancel();
+    });
+    let started = std::time::Instant::now();
+    let result = client.get_chains_with_options(&options).await;
+    assert!(matches!(result, Err(KrystalApiError::Cancelled)));
+    assert!(started.elapsed() < Duration::from_millis(150));
+    canceller.await.unwrap();
+
+    let options = RequestOptions::new().deadline_in(Duration::from_millis(20));
+    let result = client.get_chains_with_options(&options).await;
+    assert!(matches!(result, Err(KrystalApiError::DeadlineExceeded)));
+
+    // The client itself is still usable
+    assert_eq!(client.get_chains().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_request_options_timeout_and_retry_override() {
+    use krystal_cli::RequestOptions;
+
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_latency(Duration::from_millis(100))
+            .with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}])),
+    );
+    let client = mock_client(transport.clone());
+
+    let options = RequestOptions::new()
+        .timeout(Duration::from_millis(10))
+        .retry(RetryConfig {
+            max_attempts: 2,
+            base_delay: Duration::from_millis(1),
+            jitter: 0.0,
+            ..RetryConfig::default()
+        });
+    let result = client.get_chains_with_options(&options).await;
+    assert!(matches!(result, Err(KrystalApiError::Timeout(_))));
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[tokio::test]
+async fn test_request_options_bypass_cache() {
+    use krystal_cli::{MemoryCacheConfig, RequestOptions};
+
+    let transport = Arc::new(
+        MockTransport::new().with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}])),
+    );
+    let config = ClientConfig {
+        memory_cache: Some(MemoryCacheConfig::default()),
+        ..ClientConfig::default()
+    };
+    let client =
+        KrystalApiClient::with_transport("test-key".to_string(), config, transport.clone())
+            .unwrap();
+
+    client.get_chains().await.unwrap();
+    client.get_chains().await.unwrap();
+    assert_eq!(transport.request_count(), 1);
+
+    let options = RequestOptions::new().bypass_cache(true);
+    client.get_chains_with_options(&options).await.unwrap();
+    assert_eq!(transport.request_count(), 2);
+}
+
+#[tokio::test]
+async fn test_list_envelopes_parse_wrapped_and_bare_bodies() {
+    let transport = Arc::new(
+        MockTransport::new()
+            .with_json(
+                "/v1/pools",
+                json!({
+                    "meta": {"chains": [1, 2]},
+                    "pools": [{"poolAddress": "0x1", "poolPrice": 1.0, "feeTier": 500, "tvl": 5.0}],
+                    "total": 3
+                }),
+            )
+            .with_json("/v1/chains", json!({"chains": [{"id": 1, "name": "Ethereum"}]}))
+            .with_json("/v1/chains/1", json!({"chain": {"id": 1, "name": "Ethereum"}}))
+            .with_response("/v1/protocols", HttpResponse::new(200, "{\"protocols\": [")),
+    );
+    let client = mock_client(transport);
+
+    let page = client
+        .get_pools_paginated(PoolsQuery::new().chain_id(1).limit(1))
+        .await
+        .unwrap();
+    assert_eq!(page.data.len(), 1);
+    assert_eq!(page.total, Some(3));
+    assert_eq!(page.has_more, Some(true));
+
+    assert_eq!(client.get_chains().await.unwrap()[0].name, "Ethereum");
+    assert_eq!(client.get_chain_stats(1).await.unwrap().id, Some(1));
+    assert!(client.get_protocols().await.is_err());
+}
+
+#[tokio::test]
+async fn test_get_raw_passes_params_and_stays_under_base_url() {
+    let transport = Arc::new(
+        MockTransport::new().with_json("/v1/pools/1/0xabc/fees", json!({"fees": [1, 2]})),
+    );
+    let client = mock_client(transport.clone());
+
+    let json = client
+        .get_raw("/v1/pools/1/0xabc/fees", &[("protocols", "a"), ("protocols", "b")])
+        .await
+        .unwrap();
+    assert_eq!(json, json!({"fees": [1, 2]}));
+
+    let request = &transport.requests()[0];
+    assert_eq!(request.url.query(), Some("protocols=a&protocols=b"));
+    assert!(request.headers.contains_key("KC-APIKey"));
+
+    let result = client.get_raw("https://evil.example/v1/chains", &[]).await;
+    assert!(matches!(result, Err(KrystalApiError::InvalidParams(_))));
+    assert_eq!(transport.request_count(), 1);
+}
@@ -14,3 +14,6 @@ mod utils_tests;
 
 #[cfg(test)]
 mod cli_tests;
+
+#[cfg(test)]
+mod spec_tests;
@@ -145,6 +145,11 @@ fn test_transaction_query_validation() {
     let invalid_limit_high = TransactionQuery::new().limit(50000);
     assert!(invalid_limit_high.validate().is_err());
 
+    // Per-endpoint maxima such as api.json's 5000 for pool transactions are left to the
+    // spec check
+    assert!(TransactionQuery::new().limit(10000).validate().is_ok());
+    assert!(TransactionQuery::new().limit(10001).validate().is_err());
+
     let valid_query = TransactionQuery::new().time_range(1000000, 2000000);
     assert!(valid_query.validate().is_ok());
 
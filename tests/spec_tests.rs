@@ -0,0 +1,143 @@
+//! Conformance of the client's requests with the bundled API spec (offline)
+
+use krystal_cli::client::{ClientConfig, KrystalApiClient};
+use krystal_cli::models::PositionStatus;
+use krystal_cli::query::{PoolsQuery, PositionsQuery, TransactionQuery};
+use krystal_cli::transport::MockTransport;
+use krystal_cli::{ApiSpec, PoolSortBy, SpecCheck};
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+const WALLET: &str = "0x742d35Cc6639C0532fA20c00fa1A5a6f1a8f3b82";
+const POOL: &str = "0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640";
+
+fn mock_transport() -> Arc<MockTransport> {
+    let pool = json!({"poolAddress": POOL, "poolPrice": 1.0, "feeTier": 500, "tvl": 1.0});
+    Arc::new(
+        MockTransport::new()
+            .with_json("/v1/chains", json!([{"id": 1, "name": "Ethereum"}]))
+            .with_json("/v1/chains/1", json!({"id": 1, "name": "Ethereum"}))
+            .with_json("/v1/protocols", json!([]))
+            .with_json("/v1/pools", json!({"pools": [pool]}))
+            .with_json(&format!("/v1/pools/1/{POOL}"), pool)
+            .with_json(&format!("/v1/pools/1/{POOL}/historical"), json!({}))
+            .with_json(&format!("/v1/pools/1/{POOL}/transactions"), json!({"transactions": []}))
+            .with_json("/v1/positions", json!({"positions": []}))
+            .with_json("/v1/positions/1/transactions", json!({"transactions": []}))
+            .with_json("/v1/positions/1/42", json!({"id": "42"})),
+    )
+}
+
+/// Call every endpoint with every parameter the client can send
+async fn exercise_client(client: &KrystalApiClient) {
+    let range = TransactionQuery::new()
+        .time_range(1_700_000_000, 1_700_086_400)
+        .limit(5000)
+        .offset(10);
+
+    client.get_chains().await.unwrap();
+    client.get_chain_stats(1).await.unwrap();
+    client.get_protocols().await.unwrap();
+    client
+        .get_pools(
+            PoolsQuery::new()
+                .chain_id(1)
+                .protocol("uniswapv3")
+                .token(POOL)
+                .factory_address(POOL)
+                .sort_by(PoolSortBy::Tvl)
+                .min_tvl(1000.0)
+                .min_volume_24h(10.0)
+                .limit(10)
+                .offset(5)
+                .with_incentives(true),
+        )
+        .await
+        .unwrap();
+    client.get_pool_detail(1, POOL, Some(POOL), true).await.unwrap();
+    client
+        .get_pool_historical(1, POOL, Some(POOL), Some(range.clone()))
+        .await
+        .unwrap();
+    client
+        .get_pool_transactions(1, POOL, Some(POOL), Some(range.clone()))
+        .await
+        .unwrap();
+    client
+        .get_positions(
+            PositionsQuery::new(WALLET)
+                .chain_id(1)
+                .status(PositionStatus::Open)
+                .protocols(vec!["uniswapv3".to_string(), "sushiswapv3".to_string()]),
+        )
+        .await
+        .unwrap();
+    client
+        .get_position_transactions(1, Some(WALLET), POOL, Some("42"), Some(range))
+        .await
+        .unwrap();
+    // Only the request matters here; the stub body is not a full position
+    let position_id = format!("{POOL}-42").parse().unwrap();
+    let _ = client.get_position_detail(1, &position_id).await;
+}
+
+#[tokio::test]
+async fn test_every_request_matches_bundled_spec() {
+    let transport = mock_transport();
+    let client = KrystalApiClient::with_transport(
+        "test-key".to_string(),
+        ClientConfig::default(),
+        transport.clone(),
+    )
+    .unwrap();
+    exercise_client(&client).await;
+
+    let spec = ApiSpec::bundled();
+    let root = url::Url::parse("https://cloud-api.krystal.app/").unwrap();
+    let mut covered = BTreeSet::new();
+    for request in transport.requests() {
+        let violations = spec.check_url(&request.url, &root);
+        assert!(violations.is_empty(), "{}: {:?}", request.url, violations);
+        covered.insert(spec.operation(request.url.path()).unwrap().path.clone());
+    }
+
+    let documented: BTreeSet<_> = spec.operations().iter().map(|op| op.path.clone()).collect();
+    assert_eq!(covered, documented);
+}
+
+#[tokio::test]
+async fn test_strict_spec_check_sends_conforming_requests() {
+    let transport = mock_transport();
+    let config = ClientConfig {
+        spec_check: SpecCheck::Strict,
+        ..ClientConfig::default()
+    };
+    let client =
+        KrystalApiClient::with_transport("test-key".to_string(), config, transport.clone())
+            .unwrap();
+    exercise_client(&client).await;
+    assert_eq!(transport.request_count(), 10);
+}
+
+#[tokio::test]
+async fn test_strict_spec_check_enforces_endpoint_limits() {
+    let transport = mock_transport();
+    let config = ClientConfig {
+        spec_check: SpecCheck::Strict,
+        ..ClientConfig::default()
+    };
+    let client =
+        KrystalApiClient::with_transport("test-key".to_string(), config, transport.clone())
+            .unwrap();
+
+    // Within the generic cap, but over the documented 5000 for pool transactions
+    let query = TransactionQuery::new().limit(6000);
+    assert!(query.validate().is_ok());
+    let err = client
+        .get_pool_transactions(1, POOL, None, Some(query))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, krystal_cli::KrystalApiError::SpecViolation { .. }), "{err:?}");
+    assert_eq!(transport.request_count(), 0);
+}
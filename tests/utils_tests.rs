@@ -62,6 +62,28 @@ fn test_address_validation() {
     ));
 }
 
+#[test]
+fn test_address_checksum() {
+    // Reference vectors from EIP-55
+    for checksummed in [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ] {
+        assert_eq!(
+            address::to_checksum_address(&checksummed.to_lowercase()).as_deref(),
+            Some(checksummed)
+        );
+        assert!(address::is_valid_ethereum_address_checksum(checksummed));
+    }
+
+    assert!(!address::is_valid_ethereum_address_checksum(
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD" // Last letter flipped
+    ));
+    assert_eq!(address::to_checksum_address("0x123"), None);
+}
+
 #[test]
 fn test_address_normalization() {
     let addr = "0x742D35CC6639C0532FA20C00FA1A5A6F1A8F3B82";
@@ -221,6 +243,28 @@ fn test_pagination_iterator_no_more_pages() {
     assert_eq!(paginator.progress_percentage().unwrap(), 100.0);
 }
 
+#[test]
+fn test_pagination_iterator_infers_more_from_full_pages() {
+    let mut paginator = pagination::PaginationIterator::<String>::starting_at(2, 40);
+    assert_eq!(paginator.next_offset(), 40);
+
+    let page = |items: &[&str]| krystal_cli::models::PaginatedResponse {
+        data: items.iter().map(|s| s.to_string()).collect(),
+        total: None,
+        offset: None,
+        limit: None,
+        has_more: None,
+    };
+
+    paginator.update_from_response(&page(&["a", "b"]));
+    assert!(paginator.has_next_page());
+    assert_eq!(paginator.next_offset(), 42);
+
+    paginator.update_from_response(&page(&["c"]));
+    assert!(!paginator.has_next_page());
+    assert_eq!(paginator.next_offset(), 43);
+}
+
 // Async tests for retry functionality
 #[tokio::test]
 async fn test_retry_success_on_first_attempt() {
@@ -239,6 +283,7 @@ async fn test_retry_with_backoff_config() {
         base_delay: Duration::from_millis(10),
         backoff_multiplier: 2.0,
         max_delay: Duration::from_millis(100),
+        ..Default::default()
     };
 
     let attempt_count = std::cell::RefCell::new(0);
@@ -264,3 +309,59 @@ async fn test_retry_with_backoff_config() {
     assert_eq!(result.unwrap(), 42);
     assert_eq!(*attempt_count.borrow(), 2);
 }
+
+#[tokio::test]
+async fn test_retry_honours_retry_after() {
+    let config = retry::RetryConfig {
+        max_attempts: 2,
+        base_delay: Duration::from_millis(1),
+        jitter: 0.0,
+        ..Default::default()
+    };
+
+    let attempt_count = std::cell::Cell::new(0);
+    let started = std::time::Instant::now();
+    let result = retry::retry_with_backoff(config, || {
+        attempt_count.set(attempt_count.get() + 1);
+        let first = attempt_count.get() == 1;
+        async move {
+            if first {
+                Err(krystal_cli::error::KrystalApiError::RateLimited {
+                    retry_after: Some(Duration::from_millis(50)),
+                })
+            } else {
+                Ok(7)
+            }
+        }
+    })
+    .await;
+
+    assert_eq!(result.unwrap(), 7);
+    assert!(started.elapsed() >= Duration::from_millis(50));
+}
+
+#[tokio::test]
+async fn test_retry_gives_up_when_budget_exceeded() {
+    let config = retry::RetryConfig {
+        max_attempts: 5,
+        max_retry_time: Some(Duration::from_millis(100)),
+        ..Default::default()
+    };
+
+    let attempt_count = std::cell::Cell::new(0);
+    let result: krystal_cli::error::Result<()> = retry::retry_with_backoff(config, || {
+        attempt_count.set(attempt_count.get() + 1);
+        async {
+            Err(krystal_cli::error::KrystalApiError::RateLimited {
+                retry_after: Some(Duration::from_secs(30)),
+            })
+        }
+    })
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(krystal_cli::error::KrystalApiError::RateLimited { .. })
+    ));
+    assert_eq!(attempt_count.get(), 1);
+}